@@ -3,12 +3,12 @@ extern crate proc_macro;
 mod parse;
 
 use {
-    parse::{parse_attr, parse_fields, NamedField, TypeComplexity},
+    parse::{parse_attr, parse_enum_attrs, parse_fields, NamedField, Reference, TypeComplexity},
     proc_macro::TokenStream,
     proc_macro2::Span,
     quote::{format_ident, quote},
     std::fmt,
-    syn::{parse_macro_input, DeriveInput, Ident, LitStr, Type},
+    syn::{parse_macro_input, spanned::Spanned, Data, DeriveInput, Fields, Ident, LitStr, Type},
 };
 
 struct Error(Option<syn::Error>);
@@ -67,7 +67,7 @@ pub fn derive_model_data(input: TokenStream) -> TokenStream {
     .into()
 }
 
-#[proc_macro_derive(FromQueryResult)]
+#[proc_macro_derive(FromQueryResult, attributes(mysql_connector))]
 pub fn derive_from_query_result(input: TokenStream) -> TokenStream {
     let mut error = Error::empty();
     let input = parse_macro_input!(input as DeriveInput);
@@ -88,7 +88,37 @@ pub fn derive_from_query_result(input: TokenStream) -> TokenStream {
         .filter(TypeComplexity::simple_ref)
         .map(|x| &x.ident)
         .collect();
+    let simple_field_columns: &Vec<LitStr> = &fields
+        .iter()
+        .filter(TypeComplexity::simple_ref)
+        .map(NamedField::column_lit)
+        .collect();
+    let simple_field_values = fields
+        .iter()
+        .filter(TypeComplexity::simple_ref)
+        .map(|field| {
+            let field_ident = &field.ident;
+            let value = match &field.attrs.with {
+                Some(with) => quote!(#with::from_value(row[i].take()).map_err(Into::<mysql_connector::error::ParseError>::into)?),
+                None => quote!(row[i].take().try_into()?),
+            };
+            let missing = match field.attrs.default_value() {
+                Some(default) => default,
+                None => quote! {
+                    return Err(mysql_connector::error::ParseError::MissingField(
+                        concat!(stringify!(#ident), ".", stringify!(#field_ident))
+                    ))
+                },
+            };
+            quote! {
+                match mapping.#field_ident {
+                    Some(i) => #value,
+                    None => #missing,
+                }
+            }
+        });
     let mut struct_field_names = Vec::new();
+    let mut struct_field_columns = Vec::new();
     let mut set_struct_fields = proc_macro2::TokenStream::new();
     for field in &fields {
         if let TypeComplexity::Struct(r#struct) = &field.complexity {
@@ -99,6 +129,11 @@ pub fn derive_from_query_result(input: TokenStream) -> TokenStream {
                 .iter()
                 .map(|x| format_ident!("{}_{}", field.ident, x.1));
             struct_field_names.extend(mapping_names.clone());
+            struct_field_columns.extend(
+                r#struct.fields.iter().map(|x| {
+                    LitStr::new(&format!("{}_{}", field.column(), x.1), field.ident.span())
+                }),
+            );
             let struct_names = r#struct.fields.iter().map(|x| &x.0);
             set_struct_fields = quote! {
                 #set_struct_fields
@@ -117,47 +152,151 @@ pub fn derive_from_query_result(input: TokenStream) -> TokenStream {
         .filter(TypeComplexity::complex_ref)
         .map(|x: &parse::NamedField| &x.ident)
         .collect();
-    let complex_field_types: &Vec<&Type> = &fields
+    let complex_fields: &Vec<(&Ident, &Reference)> = &fields
         .iter()
-        .filter(TypeComplexity::complex_ref)
-        .map(|x| &x.ty)
+        .filter_map(|field| field.complexity.reference().map(|x| (&field.ident, x)))
+        .collect();
+    // boxed mappings are created when a column is mapped, as self-referential mappings would be infinite
+    let complex_field_mapping_types = complex_fields.iter().map(|(_, reference)| {
+        let ty = &reference.model;
+        let mapping = quote!(<#ty as mysql_connector::model::FromQueryResult>::Mapping);
+        if reference.boxed {
+            quote!(Option<std::boxed::Box<#mapping>>)
+        } else {
+            mapping
+        }
+    });
+    // boxed references beyond their depth only select the column, so a reference that isn't loaded is detected
+    let key_field_names: &Vec<Ident> = &complex_fields
+        .iter()
+        .filter(|(_, reference)| reference.boxed)
+        .map(|(ident, _)| format_ident!("{ident}_foreign_key"))
+        .collect();
+    let key_field_columns: &Vec<LitStr> = &fields
+        .iter()
+        .filter(|x| x.complexity.reference().is_some_and(|x| x.boxed))
+        .map(NamedField::column_lit)
         .collect();
+    let complex_field_all_null = complex_fields.iter().map(|(ident, reference)| {
+        if reference.boxed {
+            quote! {
+                match &self.#ident {
+                    Some(mapping) => mysql_connector::model::FromQueryResultMapping::all_null(&**mapping, row),
+                    None => true,
+                }
+            }
+        } else {
+            quote!(mysql_connector::model::FromQueryResultMapping::all_null(&self.#ident, row))
+        }
+    });
+    let complex_field_values = complex_fields.iter().map(|(field_ident, reference)| {
+        let ty = &reference.model;
+        let mapping = if reference.boxed {
+            quote! {
+                mapping.#field_ident.as_deref()
+            }
+        } else {
+            quote!(Some(&mapping.#field_ident))
+        };
+        let mut value = quote! {
+            <#ty as mysql_connector::model::FromQueryResult>::from_mapping_and_row(mapping, row)?
+        };
+        if reference.boxed {
+            value = quote!(std::boxed::Box::new(#value));
+        }
+        if reference.boxed {
+            let key = format_ident!("{field_ident}_foreign_key");
+            quote! {
+                match #mapping {
+                    Some(mapping) if !mysql_connector::model::FromQueryResultMapping::all_null(mapping, row) => Some(#value),
+                    _ => match mapping.#key {
+                        Some(i) if !matches!(row[i], mysql_connector::types::Value::Null) => {
+                            return Err(mysql_connector::error::ParseError::MissingField(
+                                concat!(stringify!(#ident), ".", stringify!(#field_ident))
+                            ))
+                        }
+                        _ => None,
+                    },
+                }
+            }
+        } else if reference.optional {
+            quote! {
+                match #mapping {
+                    Some(mapping) if !mysql_connector::model::FromQueryResultMapping::all_null(mapping, row) => Some(#value),
+                    _ => None,
+                }
+            }
+        } else {
+            quote! {
+                match #mapping {
+                    Some(mapping) => #value,
+                    None => return Err(mysql_connector::error::ParseError::MissingField(
+                        concat!(stringify!(#ident), ".", stringify!(#field_ident))
+                    )),
+                }
+            }
+        }
+    });
+
+    // children are loaded with a separate query, skipped fields aren't loaded at all
+    let default_field_names = fields
+        .iter()
+        .filter(|x| {
+            matches!(
+                x.complexity,
+                TypeComplexity::HasMany(_) | TypeComplexity::Skip
+            )
+        })
+        .map(|x| &x.ident);
+    let default_field_values = fields
+        .iter()
+        .filter(|x| {
+            matches!(
+                x.complexity,
+                TypeComplexity::HasMany(_) | TypeComplexity::Skip
+            )
+        })
+        .map(|x| {
+            x.attrs
+                .default_value()
+                .unwrap_or_else(|| quote!(std::default::Default::default()))
+        });
 
     let set_mapping = {
         let mut set_child_mapping = proc_macro2::TokenStream::new();
 
-        for (
-            i,
-            NamedField {
-                complexity: _,
-                //vis: _,
-                ident,
-                ty: _,
-            },
-        ) in fields
+        for (i, field) in fields
             .iter()
             .filter(TypeComplexity::complex_ref)
             .enumerate()
         {
-            let name = ident.to_string();
+            let ident = &field.ident;
+            let name = field.column();
             let name_with_point = name.clone() + ".";
-            let len = name_with_point.as_bytes().len();
             let maybe_else = if i == 0 { None } else { Some(quote!(else)) };
 
+            let mapping = match field.complexity.reference() {
+                Some(reference) if reference.boxed => {
+                    quote!(&mut **self.#ident.get_or_insert_with(Default::default))
+                }
+                _ => quote!(&mut self.#ident),
+            };
+            // the table isn't stripped by `set_mapping`, as the aliases are relative to the root table
             set_child_mapping = quote! {
                 #set_child_mapping
                 #maybe_else if table == #name {
-                    self.#ident.set_mapping(column, "", index);
-                } else if table.starts_with(#name_with_point) {
-                    self.#ident.set_mapping(column, &table[#len..], index);
+                    mysql_connector::model::FromQueryResultMapping::set_mapping_inner(#mapping, column, "", index);
+                } else if let Some(table) = table.strip_prefix(#name_with_point) {
+                    mysql_connector::model::FromQueryResultMapping::set_mapping_inner(#mapping, column, table, index);
                 }
             };
         }
 
         let set_own_mapping = quote! {
             *match column.org_name() {
-                #(stringify!(#simple_field_names) => &mut self.#simple_field_names,)*
-                #(stringify!(#struct_field_names) => &mut self.#struct_field_names,)*
+                #(#simple_field_columns => &mut self.#simple_field_names,)*
+                #(#struct_field_columns => &mut self.#struct_field_names,)*
+                #(#key_field_columns => &mut self.#key_field_names,)*
                 _ => return,
             } = Some(index);
         };
@@ -180,10 +319,38 @@ pub fn derive_from_query_result(input: TokenStream) -> TokenStream {
             #visibility struct #mapping_ident {
                 #(#simple_field_names: Option<usize>,)*
                 #(#struct_field_names: Option<usize>,)*
-                #(#complex_field_names: <#complex_field_types as mysql_connector::model::FromQueryResult>::Mapping,)*
+                #(#complex_field_names: #complex_field_mapping_types,)*
+                #(#key_field_names: Option<usize>,)*
             }
 
             impl mysql_connector::model::FromQueryResultMapping<#ident> for #mapping_ident {
+                fn all_null(&self, row: &[mysql_connector::types::Value]) -> bool {
+                    true
+                    #(&& match self.#simple_field_names {
+                        Some(i) => matches!(row[i], mysql_connector::types::Value::Null),
+                        None => true,
+                    })*
+                    #(&& match self.#struct_field_names {
+                        Some(i) => matches!(row[i], mysql_connector::types::Value::Null),
+                        None => true,
+                    })*
+                    #(&& match self.#key_field_names {
+                        Some(i) => matches!(row[i], mysql_connector::types::Value::Null),
+                        None => true,
+                    })*
+                    #(&& #complex_field_all_null)*
+                }
+
+                fn set_mapping(&mut self, column: &mysql_connector::types::Column, table: &str, index: usize) {
+                    self.set_mapping_inner(
+                        column,
+                        table
+                            .strip_prefix(<#ident as mysql_connector::model::ModelData>::TABLE_WITH_POINT)
+                            .unwrap_or(table),
+                        index,
+                    )
+                }
+
                 fn set_mapping_inner(&mut self, column: &mysql_connector::types::Column, table: &str, index: usize) {
                     #set_mapping
                 }
@@ -194,11 +361,10 @@ pub fn derive_from_query_result(input: TokenStream) -> TokenStream {
 
                 fn from_mapping_and_row(mapping: &Self::Mapping, row: &mut std::vec::Vec<mysql_connector::types::Value>) -> std::result::Result<Self, mysql_connector::error::ParseError> {
                     Ok(Self {
-                        #(#simple_field_names: row[mapping.#simple_field_names.ok_or(mysql_connector::error::ParseError::MissingField(
-                            concat!(stringify!(#ident), ".", stringify!(#simple_field_names))
-                        ))?].take().try_into()?,)*
+                        #(#simple_field_names: #simple_field_values,)*
                         #set_struct_fields
-                        #(#complex_field_names: <#complex_field_types>::from_mapping_and_row(&mapping.#complex_field_names, row)?,)*
+                        #(#complex_field_names: #complex_field_values,)*
+                        #(#default_field_names: #default_field_values,)*
                     })
                 }
             }
@@ -206,7 +372,7 @@ pub fn derive_from_query_result(input: TokenStream) -> TokenStream {
     }.into()
 }
 
-#[proc_macro_derive(ActiveModel)]
+#[proc_macro_derive(ActiveModel, attributes(mysql_connector))]
 pub fn derive_active_model(input: TokenStream) -> TokenStream {
     let mut error = Error::empty();
     let input = parse_macro_input!(input as DeriveInput);
@@ -243,13 +409,13 @@ pub fn derive_active_model(input: TokenStream) -> TokenStream {
             let names = r#struct
                 .fields
                 .iter()
-                .map(|(_, x)| format_ident!("{ident}_{x}"));
+                .map(|(_, x)| LitStr::new(&format!("{}_{x}", field.column()), ident.span()));
             insert_struct_fields = quote! {
                 #insert_struct_fields
                 match self.#ident {
                     mysql_connector::model::ActiveValue::Unset =>(),
                     mysql_connector::model::ActiveValue::Set(value) => {
-                        #(values.push(mysql_connector::model::NamedValue(stringify!(#names), value.#idents.try_into().map_err(Into::<mysql_connector::error::SerializeError>::into)?));)*
+                        #(values.push(mysql_connector::model::NamedValue(#names, value.#idents.try_into().map_err(Into::<mysql_connector::error::SerializeError>::into)?));)*
                     }
                 }
             };
@@ -279,6 +445,36 @@ pub fn derive_active_model(input: TokenStream) -> TokenStream {
             }
         })
         .unwrap_or_else(|| (simple_field_names.clone(), proc_macro2::TokenStream::new()));
+    let insert_simple_fields = fields
+        .iter()
+        .filter(TypeComplexity::simple_ref)
+        .map(|field| {
+            let ident = &field.ident;
+            let column = field.column_lit();
+            match &field.attrs.with {
+                Some(with) => quote! {
+                    match self.#ident {
+                        mysql_connector::model::ActiveValue::Unset => (),
+                        mysql_connector::model::ActiveValue::Set(value) => {
+                            values.push(mysql_connector::model::NamedValue(#column, #with::into_value(value).map_err(Into::<mysql_connector::error::SerializeError>::into)?));
+                        }
+                    }
+                },
+                None => quote! {
+                    self.#ident.insert_named_value(&mut values, #column)?;
+                },
+            }
+        });
+    let primary_column = match primary
+        .as_ref()
+        .and_then(|(primary, _)| fields.iter().find(|field| field.ident == *primary))
+    {
+        Some(field) => {
+            let column = field.column_lit();
+            quote!(Some(#column))
+        }
+        None => quote!(None),
+    };
     let get_primary = match primary {
         Some((primary, _)) => quote! {
             match self.#primary {
@@ -309,11 +505,66 @@ pub fn derive_active_model(input: TokenStream) -> TokenStream {
         .filter(TypeComplexity::complex_ref)
         .map(|x| &x.ident)
         .collect();
-    let complex_field_types: &Vec<&Type> = &fields
+    // boxed, as self-referential active models would be infinite
+    let complex_field_types = fields
+        .iter()
+        .filter_map(|x| x.complexity.reference())
+        .map(|x| {
+            let ty = &x.model;
+            let reference = quote!(mysql_connector::model::ActiveReference<#ty>);
+            if x.boxed {
+                quote!(std::boxed::Box<#reference>)
+            } else {
+                reference
+            }
+        });
+    let complex_field_columns: &Vec<LitStr> = &fields
         .iter()
         .filter(TypeComplexity::complex_ref)
-        .map(|x| &x.ty)
+        .map(NamedField::column_lit)
         .collect();
+    let complex_field_values = fields
+        .iter()
+        .filter_map(|field| field.complexity.reference().map(|x| (&field.ident, x)))
+        .map(|(ident, reference)| {
+            if reference.boxed {
+                quote!((*self.#ident))
+            } else {
+                quote!(self.#ident)
+            }
+        });
+    let complex_field_references = fields
+        .iter()
+        .filter_map(|field| field.complexity.reference().map(|x| (&field.ident, x)))
+        .map(|(ident, reference)| {
+            let ty = &reference.model;
+            let value = if reference.boxed {
+                quote!(*value)
+            } else {
+                quote!(value)
+            };
+            let insert = quote! {
+                mysql_connector::model::ActiveReference::Insert(<#ty as mysql_connector::model::HasActiveModel>::into_active_model(#value))
+            };
+            let value = if reference.optional {
+                quote! {
+                    match self.#ident {
+                        Some(value) => #insert,
+                        None => mysql_connector::model::ActiveReference::Unset,
+                    }
+                }
+            } else {
+                quote! {{
+                    let value = self.#ident;
+                    #insert
+                }}
+            };
+            if reference.boxed {
+                quote!(std::boxed::Box::new(#value))
+            } else {
+                value
+            }
+        });
 
     let ident = &input.ident;
     let model_ident = format_ident!("{ident}ActiveModel");
@@ -324,15 +575,17 @@ pub fn derive_active_model(input: TokenStream) -> TokenStream {
             pub struct #model_ident {
                 #(pub #simple_field_names: mysql_connector::model::ActiveValue<#simple_field_types>,)*
                 #(pub #struct_field_names: mysql_connector::model::ActiveValue<#struct_field_types>,)*
-                #(pub #complex_field_names: mysql_connector::model::ActiveReference<#complex_field_types>,)*
+                #(pub #complex_field_names: #complex_field_types,)*
             }
 
             impl mysql_connector::model::ActiveModel<#ident> for #model_ident {
+                const PRIMARY: Option<&'static str> = #primary_column;
+
                 async fn into_values(self, conn: &mut mysql_connector::Connection) -> Result<Vec<mysql_connector::model::NamedValue>, mysql_connector::error::Error> {
                     let mut values = Vec::new();
-                    #(self.#simple_field_names.insert_named_value(&mut values, stringify!(#simple_field_names))?;)*
+                    #(#insert_simple_fields)*
                     #insert_struct_fields
-                    #(self.#complex_field_names.insert_named_value(&mut values, stringify!(#complex_field_names), conn).await?;)*
+                    #(#complex_field_values.insert_named_value(&mut values, #complex_field_columns, conn).await?;)*
                     Ok(values)
                 }
 
@@ -349,7 +602,7 @@ pub fn derive_active_model(input: TokenStream) -> TokenStream {
                         #set_primary
                         #(#simple_field_names_without_primary: mysql_connector::model::ActiveValue::Set(self.#simple_field_names_without_primary),)*
                         #(#struct_field_names: mysql_connector::model::ActiveValue::Set(self.#struct_field_names),)*
-                        #(#complex_field_names: mysql_connector::model::ActiveReference::Insert(<#complex_field_types as mysql_connector::model::HasActiveModel>::into_active_model(self.#complex_field_names)),)*
+                        #(#complex_field_names: #complex_field_references,)*
                     }
                 }
             }
@@ -357,7 +610,21 @@ pub fn derive_active_model(input: TokenStream) -> TokenStream {
     }.into()
 }
 
-#[proc_macro_derive(IntoQuery)]
+/// Functions of the model traits, which would be shadowed by column accessors with the same name.
+const RESERVED_ACCESSORS: &[&str] = &[
+    "build_query",
+    "select",
+    "from_mapping_and_row",
+    "primary",
+    "active_model",
+    "update_model",
+    "find_by_primary",
+    "delete",
+    "delete_by_primary",
+    "into_active_model",
+];
+
+#[proc_macro_derive(IntoQuery, attributes(mysql_connector))]
 pub fn derive_into_query(input: TokenStream) -> TokenStream {
     let mut error = Error::empty();
     let input = parse_macro_input!(input as DeriveInput);
@@ -365,53 +632,175 @@ pub fn derive_into_query(input: TokenStream) -> TokenStream {
     let (_, _, types) = parse_attr(&mut error, input.ident.span(), &input.attrs);
     let fields = parse_fields(&mut error, input.ident.span(), &input.data, &types);
 
+    for field in &fields {
+        let accessors = match &field.complexity {
+            TypeComplexity::Skip => Vec::new(),
+            TypeComplexity::Struct(r#struct) => r#struct
+                .fields
+                .iter()
+                .map(|x| format_ident!("{}_{}", field.ident, x.1))
+                .collect(),
+            _ => vec![field.ident.clone()],
+        };
+        for accessor in accessors {
+            if RESERVED_ACCESSORS.iter().any(|x| accessor == x) {
+                error.add(
+                    field.ident.span(),
+                    format!(
+                        "column accessor `{accessor}` collides with a function of the model traits, rename the field and keep the column with #[mysql_connector(rename = \"...\")]"
+                    ),
+                );
+            }
+        }
+    }
+
     if let Some(error) = error.error() {
         return error.into_compile_error().into();
     }
 
-    let mut simple_field_names: Vec<LitStr> = fields
+    let simple_field_columns: &Vec<LitStr> = &fields
         .iter()
         .filter(TypeComplexity::simple_ref)
-        .map(|x| LitStr::new(&x.ident.to_string(), x.ident.span()))
+        .map(NamedField::column_lit)
         .collect();
-    for field in &fields {
-        if let TypeComplexity::Struct(r#struct) = &field.complexity {
-            let mapping_names = r#struct
+    let struct_field_columns: &Vec<LitStr> = &fields
+        .iter()
+        .filter_map(|field| {
+            field
+                .complexity
+                .struct_type()
+                .map(|r#struct| (field, r#struct))
+        })
+        .flat_map(|(field, r#struct)| {
+            r#struct
                 .fields
                 .iter()
-                .map(|x| LitStr::new(&format!("{}_{}", field.ident, x.1), field.ident.span()));
-            simple_field_names.extend(mapping_names);
-        }
-    }
+                .map(|x| LitStr::new(&format!("{}_{}", field.column(), x.1), field.ident.span()))
+        })
+        .collect();
     let complex_field_names: &Vec<LitStr> = &fields
         .iter()
         .filter(TypeComplexity::complex_ref)
-        .map(|x| LitStr::new(&x.ident.to_string(), x.ident.span()))
+        .map(NamedField::column_lit)
         .collect();
     let complex_field_types: &Vec<&Type> = &fields
+        .iter()
+        .filter_map(|x| x.complexity.reference())
+        .map(|x| &x.model)
+        .collect();
+    let complex_field_optional: &Vec<bool> = &fields
+        .iter()
+        .filter_map(|x| x.complexity.reference())
+        .map(|x| x.optional)
+        .collect();
+    let complex_field_depth =
+        fields
+            .iter()
+            .filter_map(|x| x.complexity.reference())
+            .map(|x| match x.depth {
+                Some(depth) => quote!(Some(#depth)),
+                None => quote!(None),
+            });
+    let complex_field_column_types =
+        fields
+            .iter()
+            .filter_map(|x| x.complexity.reference())
+            .map(|x| {
+                let ty = &x.model;
+                let primary = quote!(<#ty as mysql_connector::model::Model>::Primary);
+                if x.optional {
+                    quote!(Option<#primary>)
+                } else {
+                    primary
+                }
+            });
+
+    let simple_field_idents: &Vec<&Ident> = &fields
+        .iter()
+        .filter(TypeComplexity::simple_ref)
+        .map(|x| &x.ident)
+        .collect();
+    // fields with a custom conversion can only be compared with values
+    let simple_field_types =
+        fields
+            .iter()
+            .filter(TypeComplexity::simple_ref)
+            .map(|x| match x.attrs.with {
+                Some(_) => quote!(mysql_connector::types::Value),
+                None => {
+                    let ty = &x.ty;
+                    quote!(#ty)
+                }
+            });
+    let struct_field_idents: &Vec<Ident> = &fields
+        .iter()
+        .filter_map(|field| {
+            field
+                .complexity
+                .struct_type()
+                .map(|r#struct| (field, r#struct))
+        })
+        .flat_map(|(field, r#struct)| {
+            r#struct
+                .fields
+                .iter()
+                .map(|x| format_ident!("{}_{}", field.ident, x.1))
+        })
+        .collect();
+    let complex_field_idents: &Vec<&Ident> = &fields
         .iter()
         .filter(TypeComplexity::complex_ref)
-        .map(|x| &x.ty)
+        .map(|x| &x.ident)
         .collect();
 
     let ident = &input.ident;
+    let visibility = &input.vis;
+
+    let has_many_fields = fields
+        .iter()
+        .filter_map(|field| field.complexity.has_many().map(|x| (&field.ident, x)))
+        .map(|(field_ident, has_many)| {
+            let ty = &has_many.model;
+            let foreign_key = &has_many.foreign_key;
+            quote! {
+                #visibility fn #field_ident() -> mysql_connector::model::HasMany<Self, #ty> {
+                    mysql_connector::model::HasMany::new(#foreign_key, |x| &mut x.#field_ident)
+                }
+            }
+        });
 
     quote! {
+        impl #ident {
+            #(#visibility fn #simple_field_idents() -> mysql_connector::model::ModelColumn<#simple_field_types> {
+                mysql_connector::model::ModelColumn::new(<Self as mysql_connector::model::ModelData>::TABLE, #simple_field_columns)
+            })*
+            #(#visibility fn #struct_field_idents() -> mysql_connector::model::ModelColumn<mysql_connector::types::Value> {
+                mysql_connector::model::ModelColumn::new(<Self as mysql_connector::model::ModelData>::TABLE, #struct_field_columns)
+            })*
+            #(#visibility fn #complex_field_idents() -> mysql_connector::model::ModelColumn<#complex_field_column_types> {
+                mysql_connector::model::ModelColumn::new(<Self as mysql_connector::model::ModelData>::TABLE, #complex_field_names)
+            })*
+            #(#has_many_fields)*
+        }
+
         impl mysql_connector::model::IntoQuery for #ident {
             const COLUMNS: &'static [mysql_connector::model::QueryColumn] = &[
-                #(mysql_connector::model::QueryColumn::Column(#simple_field_names),)*
+                #(mysql_connector::model::QueryColumn::Column(#simple_field_columns),)*
+                #(mysql_connector::model::QueryColumn::Column(#struct_field_columns),)*
                 #(mysql_connector::model::QueryColumn::Reference(mysql_connector::model::QueryColumnReference {
                     column: #complex_field_names,
                     table: <#complex_field_types as mysql_connector::model::ModelData>::TABLE,
                     key: <#complex_field_types as mysql_connector::model::Model>::PRIMARY,
-                    columns: <#complex_field_types as mysql_connector::model::IntoQuery>::COLUMNS,
+                    columns: || <#complex_field_types as mysql_connector::model::IntoQuery>::COLUMNS,
+                    optional: #complex_field_optional,
+                    depth: #complex_field_depth,
                 }),)*
             ];
         }
     }.into()
 }
 
-#[proc_macro_derive(Model)]
+#[proc_macro_derive(Model, attributes(mysql_connector))]
 pub fn derive_model(input: TokenStream) -> TokenStream {
     let mut error = Error::empty();
     let input = parse_macro_input!(input as DeriveInput);
@@ -419,12 +808,15 @@ pub fn derive_model(input: TokenStream) -> TokenStream {
     let (attr_span, attrs, types) = parse_attr(&mut error, input.ident.span(), &input.attrs);
     let fields = parse_fields(&mut error, input.ident.span(), &input.data, &types);
 
-    let mut primary_type = None;
+    let mut primary_field = None;
     let mut auto_increment = false;
     if let Some(span) = attr_span {
         match attrs.get("primary") {
             Some(primary) => match fields.iter().find(|field| field.ident == primary) {
-                Some(field) => primary_type = Some(&field.ty),
+                Some(field) if !TypeComplexity::simple(field) => {
+                    error.add(field.ident.span(), "primary has to be a single column")
+                }
+                Some(field) => primary_field = Some(field),
                 None => error.add(span, "primary not found in struct"),
             },
             None => error.add(
@@ -445,9 +837,10 @@ pub fn derive_model(input: TokenStream) -> TokenStream {
         return error.into_compile_error().into();
     }
 
-    let primary = attrs.get("primary").unwrap();
-    let primary_type = primary_type.unwrap();
-    let primary_ident = Ident::new(primary, Span::call_site());
+    let primary_field = primary_field.unwrap();
+    let primary = primary_field.column_lit();
+    let primary_type = &primary_field.ty;
+    let primary_ident = &primary_field.ident;
     let ident = &input.ident;
 
     quote! {
@@ -464,3 +857,134 @@ pub fn derive_model(input: TokenStream) -> TokenStream {
     }
     .into()
 }
+
+/// Converts a variant name like `rename_all` of serde.
+fn rename_variant(name: &str, rule: &str) -> Option<String> {
+    let snake_case = || {
+        let mut out = String::new();
+        for (i, c) in name.chars().enumerate() {
+            if c.is_uppercase() && i != 0 {
+                out.push('_');
+            }
+            out.extend(c.to_lowercase());
+        }
+        out
+    };
+    Some(match rule {
+        "lowercase" => name.to_lowercase(),
+        "UPPERCASE" => name.to_uppercase(),
+        "snake_case" => snake_case(),
+        "SCREAMING_SNAKE_CASE" => snake_case().to_uppercase(),
+        "kebab-case" => snake_case().replace('_', "-"),
+        _ => return None,
+    })
+}
+
+#[proc_macro_derive(MysqlEnum, attributes(mysql_connector))]
+pub fn derive_mysql_enum(input: TokenStream) -> TokenStream {
+    let mut error = Error::empty();
+    let input = parse_macro_input!(input as DeriveInput);
+
+    let attrs = parse_enum_attrs(&mut error, &input.attrs);
+    let rename_all = attrs.get("rename_all");
+    if let Some(rule) = rename_all {
+        if rename_variant("", rule).is_none() {
+            error.add(
+                input.ident.span(),
+                format!("unknown rename_all rule {rule:?}"),
+            );
+        }
+    }
+
+    let mut variants = Vec::new();
+    let mut names = Vec::new();
+    match &input.data {
+        Data::Enum(data) => {
+            for variant in &data.variants {
+                if !matches!(variant.fields, Fields::Unit) {
+                    error.add(variant.span(), "MysqlEnum only supports unit variants");
+                    continue;
+                }
+                let ident = variant.ident.to_string();
+                let name = match parse_enum_attrs(&mut error, &variant.attrs).remove("rename") {
+                    Some(name) => name,
+                    None => match rename_all {
+                        Some(rule) => rename_variant(&ident, rule).unwrap_or(ident),
+                        None => ident,
+                    },
+                };
+                variants.push(&variant.ident);
+                names.push(LitStr::new(&name, variant.ident.span()));
+            }
+        }
+        _ => error.add(
+            input.ident.span(),
+            "MysqlEnum can only be derived for enums",
+        ),
+    }
+
+    if let Some(error) = error.error() {
+        return error.into_compile_error().into();
+    }
+
+    let ident = &input.ident;
+
+    quote! {
+        impl mysql_connector::types::MysqlEnum for #ident {
+            const NAMES: &'static [&'static str] = &[#(#names,)*];
+
+            fn name(&self) -> &'static str {
+                match self {
+                    #(Self::#variants => #names,)*
+                }
+            }
+
+            fn from_name(name: &str) -> Option<Self> {
+                match name {
+                    #(#names => Some(Self::#variants),)*
+                    _ => None,
+                }
+            }
+        }
+
+        impl From<#ident> for mysql_connector::types::Value {
+            fn from(value: #ident) -> Self {
+                mysql_connector::types::MysqlEnum::to_value(&value)
+            }
+        }
+
+        impl TryInto<#ident> for mysql_connector::types::Value {
+            type Error = mysql_connector::error::ParseError;
+
+            fn try_into(self) -> std::result::Result<#ident, Self::Error> {
+                mysql_connector::types::MysqlEnum::from_value(self)
+            }
+        }
+
+        impl mysql_connector::model::FromQueryResult for #ident {
+            type Mapping = mysql_connector::model::EmptyMapping;
+
+            fn from_mapping_and_row(
+                mapping: &Self::Mapping,
+                row: &mut Vec<mysql_connector::types::Value>,
+            ) -> std::result::Result<Self, mysql_connector::error::ParseError> {
+                if mapping.len() != 1 || row.len() != 1 {
+                    return Err(mysql_connector::error::ParseError::RowLengthMismatch);
+                }
+                mysql_connector::types::MysqlEnum::from_value(row[0].take())
+            }
+        }
+
+        impl TryInto<Option<#ident>> for mysql_connector::types::Value {
+            type Error = mysql_connector::error::ParseError;
+
+            fn try_into(self) -> std::result::Result<Option<#ident>, Self::Error> {
+                match self {
+                    mysql_connector::types::Value::Null => Ok(None),
+                    x => mysql_connector::types::MysqlEnum::from_value(x).map(Some),
+                }
+            }
+        }
+    }
+    .into()
+}
@@ -4,8 +4,8 @@ use {
     std::collections::HashMap,
     syn::{
         punctuated::Punctuated, spanned::Spanned, token, Attribute, Data, Expr, ExprLit,
-        GenericArgument, Ident, Lit, Member, MetaNameValue, Path, PathArguments, PathSegment,
-        Token, Type, TypePath,
+        GenericArgument, Ident, Lit, LitStr, Member, Meta, MetaNameValue, Path, PathArguments,
+        PathSegment, Token, Type, TypePath,
     },
 };
 
@@ -14,6 +14,121 @@ pub struct NamedField {
     //pub vis: Visibility,
     pub ident: Ident,
     pub ty: Type,
+    pub attrs: FieldAttrs,
+}
+
+impl NamedField {
+    /// Name of the column, which is the field name unless renamed.
+    pub fn column(&self) -> String {
+        match &self.attrs.rename {
+            Some(rename) => rename.clone(),
+            None => self.ident.to_string(),
+        }
+    }
+
+    /// Literal of the column name.
+    pub fn column_lit(&self) -> LitStr {
+        LitStr::new(&self.column(), self.ident.span())
+    }
+}
+
+/// Attributes of a field (`#[mysql_connector(...)]`).
+#[derive(Default)]
+pub struct FieldAttrs {
+    /// Name of the column, if it differs from the field name (`rename = "..."`).
+    pub rename: Option<String>,
+    /// The field isn't stored in the database, and set to its default when loaded (`skip`).
+    pub skip: bool,
+    /// Value of the field if the column is missing (`default` or `default = "<function>"`).
+    pub default: Option<Option<Path>>,
+    /// Module with `from_value` and `into_value`, which convert the field (`with = "<module>"`).
+    pub with: Option<Path>,
+    /// The field is stored in a single column, and not a reference (`value`).
+    pub value: bool,
+    /// Foreign key of the children (`has_many = "..."`).
+    pub has_many: Option<String>,
+    /// Maximum number of models of the referenced table along a chain of references (`depth = N`).
+    pub depth: Option<usize>,
+}
+
+impl FieldAttrs {
+    /// Expression of the default value, or `None` if there is no default.
+    pub fn default_value(&self) -> Option<proc_macro2::TokenStream> {
+        self.default.as_ref().map(|default| match default {
+            Some(path) => quote::quote!(#path()),
+            None => quote::quote!(std::default::Default::default()),
+        })
+    }
+}
+
+fn parse_field_attrs(error: &mut Error, attrs: &[Attribute]) -> FieldAttrs {
+    let mut field = FieldAttrs::default();
+    for attr in attrs {
+        if !attr.path().is_ident("mysql_connector") {
+            continue;
+        }
+        let metas = match attr.parse_args_with(Punctuated::<Meta, token::Comma>::parse_terminated) {
+            Ok(x) => x,
+            Err(err) => {
+                error.add_err(err);
+                continue;
+            }
+        };
+        for meta in metas {
+            let value = match &meta {
+                Meta::Path(_) => None,
+                Meta::NameValue(MetaNameValue {
+                    value:
+                        Expr::Lit(ExprLit {
+                            attrs: _,
+                            lit: x @ (Lit::Str(_) | Lit::Int(_)),
+                        }),
+                    ..
+                }) => Some(x),
+                _ => {
+                    error.add(meta.span(), "expected ident, string or integer literal");
+                    continue;
+                }
+            };
+            let name = match meta.path().get_ident() {
+                Some(x) => x.to_string(),
+                None => {
+                    error.add(meta.path().span(), "expected ident");
+                    continue;
+                }
+            };
+            let mut parse_path = |x: &LitStr| match x.parse::<Path>() {
+                Ok(path) => Some(path),
+                Err(err) => {
+                    error.add_err(err);
+                    None
+                }
+            };
+            match (name.as_str(), value) {
+                ("rename", Some(Lit::Str(x))) => field.rename = Some(x.value()),
+                ("skip", None) => field.skip = true,
+                ("default", None) => field.default = Some(None),
+                ("default", Some(Lit::Str(x))) => field.default = parse_path(x).map(Some),
+                ("with", Some(Lit::Str(x))) => field.with = parse_path(x),
+                ("value", None) => field.value = true,
+                ("has_many", Some(Lit::Str(x))) => field.has_many = Some(x.value()),
+                ("depth", Some(Lit::Int(x))) => match x.base10_parse::<usize>() {
+                    Ok(0) => error.add(x.span(), "depth has to be at least 1"),
+                    Ok(depth) => field.depth = Some(depth),
+                    Err(err) => error.add_err(err),
+                },
+                ("rename" | "default" | "with" | "has_many", _) => {
+                    error.add(meta.span(), format!("expected {name} = \"...\""))
+                }
+                ("depth", _) => error.add(meta.span(), "expected depth = <number>"),
+                ("skip" | "value", Some(_)) => {
+                    error.add(meta.span(), format!("expected {name} without value"))
+                }
+                _ => error.add(meta.path().span(), format!("unknown attribute {name}")),
+            }
+        }
+    }
+    field
 }
 
 pub fn parse_fields(
@@ -28,13 +143,68 @@ pub fn parse_fields(
         Data::Struct(data) => match &data.fields {
             syn::Fields::Named(fields_named) => {
                 for field in &fields_named.named {
+                    let attrs = parse_field_attrs(error, &field.attrs);
+                    let ident = field.ident.clone().unwrap();
                     match &field.ty {
                         Type::Path(path) => {
+                            let mut complexity = if attrs.skip {
+                                TypeComplexity::Skip
+                            } else if let Some(foreign_key) = &attrs.has_many {
+                                match TypeComplexity::from_has_many(path, foreign_key.clone()) {
+                                    Some(x) => x,
+                                    None => {
+                                        error.add(field.ty.span(), "has_many expects a Vec");
+                                        continue;
+                                    }
+                                }
+                            } else if attrs.value || attrs.with.is_some() {
+                                TypeComplexity::Simple
+                            } else {
+                                TypeComplexity::from_path(path, types)
+                            };
+                            if attrs.default.is_some()
+                                && !matches!(
+                                    complexity,
+                                    TypeComplexity::Simple | TypeComplexity::Skip
+                                )
+                            {
+                                error.add(
+                                    ident.span(),
+                                    "default is only supported for single columns",
+                                );
+                            }
+                            if attrs.with.is_some() && attrs.has_many.is_some() {
+                                error.add(ident.span(), "with can't be combined with has_many");
+                            }
+                            // boxed references may be self-referential, so the number of joins has to be limited
+                            match &mut complexity {
+                                TypeComplexity::Complex(reference) if reference.boxed => {
+                                    if attrs.depth.is_none() {
+                                        error.add(
+                                            ident.span(),
+                                            "boxed references need #[mysql_connector(depth = N)], the maximum number of models of the referenced table along a chain of references",
+                                        );
+                                    }
+                                    if !reference.optional {
+                                        error.add(
+                                            ident.span(),
+                                            "boxed references have to be optional, as references beyond their depth aren't loaded",
+                                        );
+                                    }
+                                    reference.depth = attrs.depth;
+                                }
+                                _ if attrs.depth.is_some() => error.add(
+                                    ident.span(),
+                                    "depth is only supported for boxed references",
+                                ),
+                                _ => (),
+                            }
                             fields.push(NamedField {
-                                complexity: TypeComplexity::from_path(path, types),
+                                complexity,
                                 //vis: field.vis.clone(),
-                                ident: field.ident.clone().unwrap(),
+                                ident,
                                 ty: field.ty.clone(),
+                                attrs,
                             });
                         }
                         _ => error.add(
@@ -141,14 +311,94 @@ pub fn parse_attr(
     (None, map, types)
 }
 
+/// Parses the string values of `#[mysql_connector(...)]` attributes on enums and their variants.
+pub fn parse_enum_attrs(error: &mut Error, attrs: &[Attribute]) -> HashMap<String, String> {
+    let mut map = HashMap::new();
+    for attr in attrs {
+        if attr.path().is_ident("mysql_connector") {
+            match attr.parse_args_with(Punctuated::<MetaNameValue, token::Comma>::parse_terminated)
+            {
+                Ok(values) => {
+                    for value in values {
+                        let ident = match value.path.get_ident() {
+                            Some(x) => x.to_string(),
+                            None => {
+                                error.add(value.path.span(), "expected ident");
+                                continue;
+                            }
+                        };
+                        if let Expr::Lit(ExprLit {
+                            attrs: _,
+                            lit: Lit::Str(x),
+                        }) = value.value
+                        {
+                            map.insert(ident, x.value());
+                        } else {
+                            error.add(value.value.span(), "expected string literal");
+                        }
+                    }
+                }
+                Err(err) => error.add_err(err),
+            }
+        }
+    }
+    map
+}
+
+/// Field that references another model.
+#[derive(Clone)]
+pub struct Reference {
+    /// Referenced model, without `Option` and `Box`.
+    pub model: Type,
+    /// The field is an `Option`, which is `None` if the reference is `NULL`.
+    pub optional: bool,
+    /// The model is boxed, which is needed for self-referential models.
+    pub boxed: bool,
+    /// Maximum number of models of the referenced table along a chain of references,
+    /// which is required for boxed references (`#[mysql_connector(depth = N)]`).
+    pub depth: Option<usize>,
+}
+
+/// Field that contains the models which reference this model (`#[mysql_connector(has_many = "...")]`).
+#[derive(Clone)]
+pub struct HasMany {
+    /// Model of the children, without `Vec`.
+    pub model: Type,
+    /// Column of the children, which references this model.
+    pub foreign_key: String,
+}
+
 #[derive(Clone)]
 pub enum TypeComplexity {
     Simple,
     Struct(SimpleStruct),
-    Complex,
+    Complex(Reference),
+    HasMany(HasMany),
+    /// The field isn't stored in the database (`#[mysql_connector(skip)]`).
+    Skip,
 }
 
 impl TypeComplexity {
+    /// Returns `None` if the type isn't a `Vec`.
+    pub fn from_has_many(path: &TypePath, foreign_key: String) -> Option<Self> {
+        let segment = path.path.segments.last()?;
+        if segment.ident != "Vec" {
+            return None;
+        }
+        match &segment.arguments {
+            PathArguments::AngleBracketed(args) if args.args.len() == 1 => {
+                match args.args.first().unwrap() {
+                    GenericArgument::Type(model) => Some(Self::HasMany(HasMany {
+                        model: model.clone(),
+                        foreign_key,
+                    })),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
     pub fn from_path(path: &TypePath, types: &[SimpleStruct]) -> Self {
         fn path_eq(a: &Path, b: &Path) -> bool {
             if a.segments.len() != b.segments.len() {
@@ -208,7 +458,17 @@ impl TypeComplexity {
                 &["chrono", "NaiveDate"],
                 &["chrono", "NaiveDateTime"],
                 &["chrono", "Duration"],
+                &["chrono", "DateTime"],
+                &["time", "Date"],
+                &["time", "PrimitiveDateTime"],
+                &["time", "OffsetDateTime"],
                 &["mysql_connector", "types", "Hex"],
+                &["std", "collections", "HashSet"],
+                &["rust_decimal", "Decimal"],
+                &["mysql_connector", "types", "Json"],
+                &["uuid", "Uuid"],
+                &["mysql_connector", "types", "SwappedUuid"],
+                &["mysql_connector", "types", "Spatial"],
             ];
             for simple_path in SIMPLE {
                 if path_matches(path, simple_path) {
@@ -228,12 +488,34 @@ impl TypeComplexity {
             false
         }
 
+        /// Removes the wrapper type and returns the inner path.
+        fn unwrap<'a>(path: &'a Path, wrapper: &[&'static str]) -> Option<&'a Path> {
+            if path_matches(path, wrapper) {
+                get_last_arguments_path(&path.segments)
+            } else {
+                None
+            }
+        }
+
         if is_simple(&path.path, true) {
             Self::Simple
         } else if let Some(r#type) = types.iter().find(|x| path_eq(&x.path, &path.path)) {
             Self::Struct(r#type.clone())
         } else {
-            Self::Complex
+            let mut model = &path.path;
+            let optional = unwrap(model, &["std", "option", "Option"]);
+            model = optional.unwrap_or(model);
+            let boxed = unwrap(model, &["std", "boxed", "Box"]);
+            model = boxed.unwrap_or(model);
+            Self::Complex(Reference {
+                model: Type::Path(TypePath {
+                    qself: None,
+                    path: model.clone(),
+                }),
+                optional: optional.is_some(),
+                boxed: boxed.is_some(),
+                depth: None,
+            })
         }
     }
 }
@@ -247,6 +529,20 @@ impl TypeComplexity {
         }
     }
 
+    pub fn reference(&self) -> Option<&Reference> {
+        match self {
+            Self::Complex(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    pub fn has_many(&self) -> Option<&HasMany> {
+        match self {
+            Self::HasMany(x) => Some(x),
+            _ => None,
+        }
+    }
+
     pub fn simple(this: &NamedField) -> bool {
         matches!(this.complexity, Self::Simple)
     }
@@ -256,7 +552,7 @@ impl TypeComplexity {
     }
 
     pub fn complex(this: &NamedField) -> bool {
-        matches!(this.complexity, Self::Complex)
+        matches!(this.complexity, Self::Complex(_))
     }
 
     pub fn simple_ref(this: &&NamedField) -> bool {
@@ -268,6 +564,6 @@ impl TypeComplexity {
     }
 
     pub fn complex_ref(this: &&NamedField) -> bool {
-        matches!(this.complexity, Self::Complex)
+        matches!(this.complexity, Self::Complex(_))
     }
 }
@@ -1,5 +1,5 @@
 use {
-    super::{types::AuthPlugin, Connection, ParseBuf, Stream},
+    super::{types::AuthPlugin, Connection, ParseBuf},
     crate::{
         error::ProtocolError,
         packets::{AuthSwitchRequest, ErrPacket},
@@ -9,25 +9,25 @@ use {
 };
 
 impl Connection {
-    pub(super) fn continue_auth<T: Stream>(
+    pub(super) fn continue_auth(
         &mut self,
     ) -> Pin<Box<dyn Future<Output = Result<(), Error>> + '_>> {
         match self.options.auth_plugin().unwrap_or(self.data.auth_plugin) {
             #[cfg(feature = "caching-sha2-password")]
-            AuthPlugin::Sha2 => Box::pin(self.continue_caching_sha2_password_auth::<T>()),
+            AuthPlugin::Sha2 => Box::pin(self.continue_caching_sha2_password_auth()),
             AuthPlugin::Native | AuthPlugin::Clear => {
-                Box::pin(self.continue_mysql_native_password_auth::<T>())
+                Box::pin(self.continue_mysql_native_password_auth())
             }
         }
     }
 
-    async fn continue_mysql_native_password_auth<T: Stream>(&mut self) -> Result<(), Error> {
+    async fn continue_mysql_native_password_auth(&mut self) -> Result<(), Error> {
         let packet = self.read_packet().await?;
         match packet.first() {
             Some(0x00) => Ok(()),
             Some(0xFE) if !self.data.auth_switched => {
                 let auth_switch = AuthSwitchRequest::deserialize(&mut ParseBuf(&packet), ())?;
-                self.perform_auth_switch::<T>(auth_switch).await
+                self.perform_auth_switch(auth_switch).await
             }
             _ => Err(
                 match ErrPacket::deserialize(&mut ParseBuf(&packet), self.data.capabilities) {
@@ -43,7 +43,7 @@ impl Connection {
 
     #[cfg(feature = "caching-sha2-password")]
     #[cfg_attr(doc, doc(cfg(feature = "caching-sha2-password")))]
-    async fn continue_caching_sha2_password_auth<T: Stream>(&mut self) -> Result<(), Error> {
+    async fn continue_caching_sha2_password_auth(&mut self) -> Result<(), Error> {
         use {
             crate::{
                 error::SerializeError,
@@ -68,7 +68,7 @@ impl Connection {
                     pass.extend_from_slice(self.options.password().as_bytes());
                     pass.push(0);
 
-                    if T::SECURE {
+                    if self.data.secure {
                         self.write_packet(&pass).await?;
                     } else {
                         let server_key = match &self.data.server_key {
@@ -128,7 +128,7 @@ impl Connection {
             },
             Some(0xFE) if !self.data.auth_switched => {
                 let auth_switch_request = ParseBuf(&packet).parse::<AuthSwitchRequest>(())?;
-                self.perform_auth_switch::<T>(auth_switch_request).await
+                self.perform_auth_switch(auth_switch_request).await
             }
             _ => Err(
                 match ErrPacket::deserialize(&mut ParseBuf(&packet), self.data.capabilities) {
@@ -142,7 +142,7 @@ impl Connection {
         }
     }
 
-    async fn perform_auth_switch<T: Stream>(
+    async fn perform_auth_switch(
         &mut self,
         auth_switch_request: AuthSwitchRequest,
     ) -> Result<(), Error> {
@@ -167,6 +167,6 @@ impl Connection {
             self.write_packet(&[]).await?;
         }
 
-        self.continue_auth::<T>().await
+        self.continue_auth().await
     }
 }
@@ -0,0 +1,140 @@
+use {
+    super::{Connection, MAX_PAYLOAD_LEN},
+    crate::{
+        bitflags::{MariadbCapabilityFlags, StmtExecuteParamFlags},
+        connection::types::ColumnType,
+        packets::{StmtBulkExecute, StmtExecuteRequest},
+        types::SimpleValue,
+        Error,
+    },
+};
+
+/// Number of statements that are written before their responses are read.
+///
+/// The responses are small enough to fit into the socket buffers while the requests are written.
+const PIPELINE_LEN: usize = 128;
+
+/// Result of [`PreparedStatement::execute_batch`](crate::PreparedStatement::execute_batch).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BatchResult {
+    affected_rows: u64,
+    last_insert_ids: Vec<u64>,
+}
+
+impl BatchResult {
+    /// Sum of the rows affected by all executions.
+    pub fn affected_rows(&self) -> u64 {
+        self.affected_rows
+    }
+
+    /// Last insert id of every row, `0` if the row didn't generate one.
+    ///
+    /// `COM_STMT_BULK_EXECUTE` only returns the first generated id,
+    /// the following ones are derived assuming `auto_increment_increment = 1`.
+    pub fn last_insert_ids(&self) -> &[u64] {
+        &self.last_insert_ids
+    }
+}
+
+impl Connection {
+    pub(super) async fn execute_batch_unchecked<V: SimpleValue>(
+        &mut self,
+        id: u32,
+        params_len: usize,
+        rows: &[&[V]],
+    ) -> Result<BatchResult, Error> {
+        if params_len > 0
+            && self
+                .data
+                .mariadb_capabilities
+                .contains(MariadbCapabilityFlags::STMT_BULK_OPERATIONS)
+        {
+            let max_len = self.data.max_allowed_packet.min(MAX_PAYLOAD_LEN);
+            let header_len = StmtBulkExecute::<V>::header_len(params_len);
+            // rows that don't fit into a packet would need long data, which isn't supported by bulk execution
+            let fits = rows
+                .iter()
+                .all(|x| header_len + StmtBulkExecute::row_len(x) <= max_len);
+            if let Some(types) = StmtBulkExecute::param_types(params_len, rows).filter(|_| fits) {
+                return self.execute_bulk(id, &types, rows, max_len).await;
+            }
+        }
+        self.execute_pipelined(id, rows).await
+    }
+
+    /// Executes the rows with `COM_STMT_BULK_EXECUTE`, using as few packets as possible.
+    async fn execute_bulk<V: SimpleValue>(
+        &mut self,
+        id: u32,
+        types: &[(ColumnType, StmtExecuteParamFlags)],
+        mut rows: &[&[V]],
+        max_len: usize,
+    ) -> Result<BatchResult, Error> {
+        let mut result = BatchResult::default();
+        while !rows.is_empty() {
+            let mut len = StmtBulkExecute::<V>::header_len(types.len());
+            let count = rows
+                .iter()
+                .take_while(|x| {
+                    len += StmtBulkExecute::row_len(x);
+                    len <= max_len
+                })
+                .count();
+
+            self.write_command(&StmtBulkExecute::new(id, types, &rows[..count]))
+                .await?;
+            let ok = self.read_response().await??;
+            result.affected_rows += ok.affected_rows();
+            let first_id = ok.last_insert_id();
+            result.last_insert_ids.extend((0..count as u64).map(|i| {
+                if first_id == 0 {
+                    0
+                } else {
+                    first_id + i
+                }
+            }));
+            rows = &rows[count..];
+        }
+        Ok(result)
+    }
+
+    /// Writes up to [`PIPELINE_LEN`] `COM_STMT_EXECUTE`s before reading their responses.
+    async fn execute_pipelined<V: SimpleValue>(
+        &mut self,
+        id: u32,
+        rows: &[&[V]],
+    ) -> Result<BatchResult, Error> {
+        let mut result = BatchResult::default();
+        for chunk in rows.chunks(PIPELINE_LEN) {
+            let mut seq_ids = Vec::with_capacity(chunk.len());
+            for params in chunk {
+                let request = StmtExecuteRequest::new(id, params);
+                if request.as_long_data() {
+                    self.send_long_data(id, params.iter()).await?;
+                }
+                self.write_command(&request).await?;
+                seq_ids.push(self.seq_id);
+            }
+
+            // all responses have to be read to keep the connection usable
+            let mut error = None;
+            for seq_id in seq_ids {
+                // every command restarts the sequence
+                self.seq_id = seq_id;
+                match self.read_response().await? {
+                    Ok(ok) => {
+                        result.affected_rows += ok.affected_rows();
+                        result.last_insert_ids.push(ok.last_insert_id());
+                    }
+                    Err(err) => {
+                        error.get_or_insert(err);
+                    }
+                }
+            }
+            if let Some(err) = error {
+                return Err(err.into());
+            }
+        }
+        Ok(result)
+    }
+}
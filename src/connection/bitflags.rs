@@ -352,6 +352,25 @@ bitflags! {
     }
 }
 
+// MariaDB extended capabilities, sent in the last 4 bytes of the handshake filler
+// if the server doesn't set `CapabilityFlags::LONG_PASSWORD`.
+bitflags! {
+    MariadbCapability: u32 {
+        /// Client supports progress indicator.
+        const PROGRESS                       = 0x0000_0001;
+        /// Permit `COM_MULTI` protocol.
+        const COM_MULTI                      = 0x0000_0002;
+        /// Permit bulk insert with `COM_STMT_BULK_EXECUTE`.
+        const STMT_BULK_OPERATIONS           = 0x0000_0004;
+        /// Add extended metadata information.
+        const EXTENDED_TYPE_INFO             = 0x0000_0008;
+        /// Permit skipping the metadata of prepared statements.
+        const CACHE_METADATA                 = 0x0000_0010;
+        /// Permit returning all bulk individual results.
+        const BULK_UNIT_RESULTS              = 0x0000_0040;
+    }
+}
+
 bitflags! {
     CursorType: u8 {
         const NO_CURSOR  = 0_u8;
@@ -373,6 +392,15 @@ bitflags! {
     }
 }
 
+bitflags! {
+    StmtBulk: u16 {
+        /// Return the generated id and affected rows of each row.
+        const SEND_UNIT_RESULTS    = 64_u16;
+        /// Parameter types are sent once, before the rows.
+        const SEND_TYPES_TO_SERVER = 128_u16;
+    }
+}
+
 bitflags! {
     Column: u16 {
         /// Field can't be NULL.
@@ -35,4 +35,6 @@ pub enum Command {
     BinlogDumpGTID,
     ResetConnection,
     End,
+    /// MariaDB only.
+    StmtBulkExecute = 0xFA,
 }
@@ -0,0 +1,99 @@
+use {
+    super::{
+        types::{BinaryProtocol, Column, Protocol},
+        Connection,
+    },
+    crate::{
+        bitflags::StatusFlags,
+        model::{FromQueryResult, FromQueryResultMapping},
+        packets::{ErrPacket, StmtFetch},
+        Deserialize, Error, ParseBuf,
+    },
+};
+
+/// Rows of a server-side cursor, fetched in batches using `COM_STMT_FETCH`.
+///
+/// Created by [`PreparedStatement::query_cursor`](crate::PreparedStatement::query_cursor).
+/// The cursor is closed when all rows were read, or when the statement is executed, reset or closed.
+pub struct Cursor<'a, R: FromQueryResult> {
+    conn: &'a mut Connection,
+    stmt_id: u32,
+    fetch_size: u32,
+    columns: Vec<Column>,
+    mapping: R::Mapping,
+    /// Rows of the current batch are being read.
+    fetching: bool,
+    finished: bool,
+}
+
+impl<'a, R: FromQueryResult> Cursor<'a, R> {
+    pub(super) fn new(
+        conn: &'a mut Connection,
+        stmt_id: u32,
+        fetch_size: u32,
+        columns: Vec<Column>,
+        finished: bool,
+    ) -> Self {
+        let mapping = R::Mapping::from_columns(&columns);
+        Self {
+            conn,
+            stmt_id,
+            fetch_size: fetch_size.max(1),
+            columns,
+            mapping,
+            fetching: false,
+            finished,
+        }
+    }
+
+    pub async fn next(&mut self) -> Result<Option<R>, Error> {
+        loop {
+            if self.finished {
+                return Ok(None);
+            }
+            if !self.fetching {
+                self.conn
+                    .write_command(&StmtFetch::new(self.stmt_id, self.fetch_size))
+                    .await?;
+                self.conn.pending_result = true;
+                self.fetching = true;
+            }
+
+            let packet = self.conn.read_packet().await?;
+            if packet.first() == Some(&0xFF) {
+                self.conn.pending_result = false;
+                self.finished = true;
+                return Err(ErrPacket::deserialize(
+                    &mut ParseBuf(&packet),
+                    self.conn.data.capabilities,
+                )?
+                .into());
+            } else if self.conn.is_last_result_set_packet(&packet) {
+                let status = self.conn.read_result_set_end(packet)?.status();
+                self.fetching = false;
+                self.finished = status.contains(StatusFlags::LAST_ROW_SENT)
+                    || !status.contains(StatusFlags::CURSOR_EXISTS);
+            } else {
+                let mut row = BinaryProtocol::read_result_set_row(&packet, &self.columns)?;
+                self.conn.row_to_utc(&mut row);
+                return Ok(Some(R::from_mapping_and_row(&self.mapping, &mut row)?));
+            }
+        }
+    }
+
+    pub async fn collect(&mut self) -> Result<Vec<R>, Error> {
+        let mut rows = Vec::new();
+        while let Some(row) = self.next().await? {
+            rows.push(row);
+        }
+        Ok(rows)
+    }
+
+    pub fn columns(&self) -> &[Column] {
+        &self.columns
+    }
+
+    pub fn mapping(&self) -> &R::Mapping {
+        &self.mapping
+    }
+}
@@ -1,6 +1,9 @@
 use {
     super::types::AuthPlugin,
-    crate::{bitflags::CapabilityFlags, TimeoutFuture},
+    crate::{
+        bitflags::{CapabilityFlags, MariadbCapabilityFlags, StatusFlags},
+        TimeoutFuture,
+    },
     std::fmt,
 };
 
@@ -9,6 +12,8 @@ pub struct ConnectionData {
     pub(super) is_mariadb: bool,
     pub(super) version: (u16, u16, u16),
     pub(super) capabilities: CapabilityFlags,
+    /// MariaDB extended capabilities supported by both sides.
+    pub(super) mariadb_capabilities: MariadbCapabilityFlags,
     pub(super) nonce: Vec<u8>,
     #[cfg(feature = "caching-sha2-password")]
     #[cfg_attr(doc, doc(cfg(feature = "caching-sha2-password")))]
@@ -16,6 +21,10 @@ pub struct ConnectionData {
     pub(super) auth_plugin: AuthPlugin,
     pub(super) auth_switched: bool,
     pub(super) max_allowed_packet: usize,
+    /// `true` if the stream is secure or was upgraded to TLS.
+    pub(super) secure: bool,
+    /// Status flags of the last Ok or EOF packet.
+    pub(super) status: StatusFlags,
     pub(super) sleep: &'static (dyn Fn(std::time::Duration) -> TimeoutFuture + Send + Sync),
 }
 
@@ -36,6 +45,10 @@ impl ConnectionData {
         self.capabilities
     }
 
+    pub fn mariadb_capabilities(&self) -> MariadbCapabilityFlags {
+        self.mariadb_capabilities
+    }
+
     pub fn auth_plugin(&self) -> AuthPlugin {
         self.auth_plugin
     }
@@ -43,6 +56,18 @@ impl ConnectionData {
     pub fn max_allowed_packet(&self) -> usize {
         self.max_allowed_packet
     }
+
+    pub fn is_secure(&self) -> bool {
+        self.secure
+    }
+
+    pub fn status(&self) -> StatusFlags {
+        self.status
+    }
+
+    pub fn in_transaction(&self) -> bool {
+        self.status.contains(StatusFlags::IN_TRANS)
+    }
 }
 
 impl fmt::Debug for ConnectionData {
@@ -53,6 +78,7 @@ impl fmt::Debug for ConnectionData {
             .field("is_mariadb", &self.is_mariadb)
             .field("version", &self.version)
             .field("capabilities", &self.capabilities)
+            .field("mariadb_capabilities", &self.mariadb_capabilities)
             .field("nonce", &self.nonce);
         #[cfg(feature = "caching-sha2-password")]
         debug.field("server_key", &self.server_key);
@@ -60,6 +86,8 @@ impl fmt::Debug for ConnectionData {
             .field("auth_plugin", &self.auth_plugin)
             .field("auth_switched", &self.auth_switched)
             .field("max_allowed_packet", &self.max_allowed_packet)
+            .field("secure", &self.secure)
+            .field("status", &self.status)
             .finish()
     }
 }
@@ -1,38 +1,65 @@
 use {
     super::{
-        types::AuthPlugin, Command, Connection, ConnectionData, ConnectionOptions, ParseBuf,
-        Stream, BUFFER_POOL, DEFAULT_MAX_ALLOWED_PACKET,
+        time_zone::check_time_zone, types::AuthPlugin, Command, Connection, ConnectionData,
+        ConnectionOptions, ParseBuf, Stream, BUFFER_POOL, DEFAULT_MAX_ALLOWED_PACKET,
     },
     crate::{
+        bitflags::MariadbCapabilityFlags,
         packets::{HandshakePacket, HandshakeResponse},
         ConnectionOptionsTrait, Error, Serialize, StreamRequirements,
     },
     std::sync::Arc,
 };
 
+#[cfg(feature = "tls")]
+use crate::{bitflags::CapabilityFlags, error::RuntimeError, packets::SslRequest};
+
 impl Connection {
     pub async fn connect<T: Stream>(options: Arc<ConnectionOptions<T>>) -> Result<Self, Error> {
+        if let Some(time_zone) = options.time_zone {
+            check_time_zone(time_zone)?;
+        }
         let mut stream = T::connect(&options.connection).await?;
         let mut seq_id = 0;
 
-        let data = Self::handle_handshake(&mut stream, &mut seq_id, options.clone()).await?;
+        let mut data = Self::handle_handshake(&mut stream, &mut seq_id, options.clone()).await?;
+        data.secure = T::SECURE;
         let mut this = Self {
             stream: Box::new(stream),
             seq_id,
             data,
-            options,
+            options: options.clone(),
             pending_result: false,
+            pending_rollback: None,
+            stmt_cache: Default::default(),
+            pending_stmt_closes: Vec::new(),
         };
-        this.do_handshake_response().await?;
-        this.continue_auth::<T>().await?;
+        this.do_handshake_response(T::host(&options.connection))
+            .await?;
+        this.continue_auth().await?;
         this.read_settings().await?;
+        this.set_time_zone().await?;
         Ok(this)
     }
 
     pub async fn disconnect(mut self) -> Result<(), Error> {
+        // the server rolls back open transactions and closes statements when the connection is closed
+        self.pending_rollback = None;
+        self.pending_stmt_closes.clear();
         self.execute_command(Command::Quit, &[]).await
     }
 
+    /// Resets the session state, e.g. open transactions, variables and prepared statements.
+    pub async fn reset_connection(&mut self) -> Result<(), Error> {
+        // rolled back by the reset
+        self.pending_rollback = None;
+        self.pending_stmt_closes.clear();
+        self.execute_command(Command::ResetConnection, &[]).await?;
+        self.read_response().await??;
+        self.stmt_cache.clear();
+        self.set_time_zone().await
+    }
+
     async fn handle_handshake(
         stream: &mut dyn StreamRequirements,
         seq_id: &mut u8,
@@ -59,12 +86,15 @@ impl Connection {
             .parse_server_version()
             .unwrap_or(((0, 0, 0), false));
         let auth_plugin = handshake.auth_plugin().unwrap_or(AuthPlugin::Native);
+        let status = handshake.status_flags();
 
         Ok(ConnectionData {
             id: handshake.connection_id(),
             is_mariadb,
             version,
             capabilities: handshake.capabilities() & options.get_capabilities(),
+            mariadb_capabilities: handshake.mariadb_capabilities()
+                & MariadbCapabilityFlags::STMT_BULK_OPERATIONS,
             nonce: handshake.into_nonce(),
             #[cfg(feature = "caching-sha2-password")]
             server_key: options.server_key(),
@@ -73,6 +103,8 @@ impl Connection {
             max_allowed_packet: options
                 .max_allowed_packet()
                 .unwrap_or(DEFAULT_MAX_ALLOWED_PACKET),
+            secure: false,
+            status,
             #[cfg(feature = "time")]
             sleep: &sleep,
             #[cfg(not(feature = "time"))]
@@ -80,21 +112,33 @@ impl Connection {
         })
     }
 
-    async fn do_handshake_response(&mut self) -> Result<(), Error> {
-        let auth_plugin = self.options.auth_plugin().unwrap_or(self.data.auth_plugin);
-        let auth_data =
-            auth_plugin.gen_data(self.options.password(), &self.data.nonce, &*self.options)?;
+    #[cfg_attr(not(feature = "tls"), allow(unused_variables))]
+    async fn do_handshake_response(&mut self, host: Option<&str>) -> Result<(), Error> {
+        let options = self.options.clone();
+        let auth_plugin = options.auth_plugin().unwrap_or(self.data.auth_plugin);
+        let auth_data = auth_plugin.gen_data(options.password(), &self.data.nonce, &*options)?;
 
         let handshake_response = HandshakeResponse::new(
             auth_data.as_deref().unwrap_or_default(),
             self.data.version,
-            self.options.user().as_bytes(),
-            self.options.db_name().map(|x| x.as_bytes()),
+            options.user().as_bytes(),
+            options.db_name().map(|x| x.as_bytes()),
             Some(auth_plugin),
             self.data.capabilities,
             Default::default(),
             self.data.max_allowed_packet as u32,
-        );
+        )
+        .with_mariadb_capabilities(self.data.mariadb_capabilities);
+
+        #[cfg(feature = "tls")]
+        if let Some(tls) = options.tls() {
+            if !self.data.capabilities.contains(CapabilityFlags::SSL) {
+                return Err(RuntimeError::TlsUnsupported.into());
+            }
+            self.write_struct(&SslRequest::from(&handshake_response))
+                .await?;
+            self.upgrade_to_tls(tls, host).await?;
+        }
 
         let mut buf = BUFFER_POOL.get();
         handshake_response.serialize(buf.as_mut());
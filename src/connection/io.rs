@@ -62,7 +62,7 @@ impl Connection {
         dst.resize(start + chunk_len, 0);
         Timeout::new(stream.read_exact(&mut dst[start..]), sleep, timeout).await??;
 
-        if dst.len() % MAX_PAYLOAD_LEN == 0 {
+        if dst.len().is_multiple_of(MAX_PAYLOAD_LEN) {
             Ok((seq_id, false))
         } else {
             Ok((seq_id, true))
@@ -105,7 +105,7 @@ impl Connection {
     }
 
     pub(super) async fn write_packet(&mut self, mut bytes: &[u8]) -> Result<(), Error> {
-        let extra_packet = bytes.remaining() % MAX_PAYLOAD_LEN == 0;
+        let extra_packet = bytes.remaining().is_multiple_of(MAX_PAYLOAD_LEN);
 
         while bytes.has_remaining() {
             let chunk_len = usize::min(bytes.remaining(), MAX_PAYLOAD_LEN);
@@ -159,7 +159,11 @@ impl Connection {
             return Err(ProtocolError::eof().into());
         }
         match packet[0] {
-            0x00 => Ok(Ok(OkPacket::read_ok(packet, capabilities)?)),
+            0x00 => {
+                let ok = OkPacket::read_ok(packet, capabilities)?;
+                self.data.status = ok.status();
+                Ok(Ok(ok))
+            }
             0xFF => Ok(Err(ErrPacket::deserialize(
                 &mut ParseBuf(packet),
                 capabilities,
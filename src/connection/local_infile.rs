@@ -0,0 +1,61 @@
+use {
+    super::{Connection, MAX_PAYLOAD_LEN},
+    crate::{error::RuntimeError, Error},
+    std::{fmt, future::Future, io, pin::Pin},
+    tokio::io::{AsyncRead, AsyncReadExt},
+};
+
+pub type LocalInfileReader = Box<dyn AsyncRead + Unpin + Send>;
+
+/// Provides the files requested by `LOAD DATA LOCAL INFILE`.
+///
+/// Setting [`ConnectionOptions::local_infile`](crate::ConnectionOptions::local_infile)
+/// enables the `LOCAL_FILES` capability.
+pub trait LocalInfileHandler: fmt::Debug + Send + Sync {
+    /// Opens the file with the name from the query.
+    ///
+    /// Return `None` to reject the request. As the server decides which file is requested,
+    /// the name should be checked against an allow-list.
+    fn open<'a>(
+        &'a self,
+        file_name: &'a str,
+    ) -> Pin<Box<dyn Future<Output = io::Result<Option<LocalInfileReader>>> + Send + 'a>>;
+}
+
+const CHUNK_SIZE: usize = 64 * 1024;
+
+impl Connection {
+    /// Sends the requested file, terminated by an empty packet.
+    ///
+    /// Errors of the handler are returned as `Ok(Some(_))`, as the response of the server has to be read anyway.
+    pub(super) async fn send_local_infile(
+        &mut self,
+        file_name: &[u8],
+    ) -> Result<Option<Error>, Error> {
+        let rejected =
+            || RuntimeError::LocalInfileRejected(String::from_utf8_lossy(file_name).into_owned());
+        let reader = match (self.options.local_infile(), std::str::from_utf8(file_name)) {
+            (Some(handler), Ok(file_name)) => handler.open(file_name).await,
+            _ => Ok(None),
+        };
+        let error = match reader {
+            Ok(Some(mut reader)) => {
+                let chunk_size = CHUNK_SIZE
+                    .min(self.data.max_allowed_packet.saturating_sub(4))
+                    .clamp(1, MAX_PAYLOAD_LEN - 1);
+                let mut buf = vec![0; chunk_size];
+                loop {
+                    match reader.read(&mut buf).await {
+                        Ok(0) => break None,
+                        Ok(len) => self.write_packet(&buf[..len]).await?,
+                        Err(err) => break Some(RuntimeError::LocalInfileIo(err).into()),
+                    }
+                }
+            }
+            Ok(None) => Some(rejected().into()),
+            Err(err) => Some(RuntimeError::LocalInfileIo(err).into()),
+        };
+        self.write_packet(&[]).await?;
+        Ok(error)
+    }
+}
@@ -1,9 +1,12 @@
 mod auth;
+mod batch;
 pub(super) mod bitflags;
 mod command;
+mod cursor;
 mod data;
 mod init;
 mod io;
+mod local_infile;
 mod options;
 pub(super) mod packets;
 mod parse_buf;
@@ -11,8 +14,19 @@ mod prepared_statement;
 mod query;
 mod result_set;
 mod serialization;
+mod stmt_cache;
+mod time_zone;
 pub mod timeout;
+#[cfg(feature = "tls")]
+#[cfg_attr(doc, doc(cfg(feature = "tls")))]
+mod tls;
+mod transaction;
 pub mod types;
+#[cfg(feature = "tcpstream")]
+mod url;
+
+#[cfg(all(test, unix, feature = "unix-socket", feature = "time"))]
+mod test;
 
 const MAX_PAYLOAD_LEN: usize = 16_777_215;
 const DEFAULT_MAX_ALLOWED_PACKET: usize = 4 * 1024 * 1024;
@@ -40,19 +54,32 @@ pub(crate) use {
 };
 
 pub use {
+    batch::BatchResult,
+    cursor::Cursor,
     data::ConnectionData,
+    local_infile::{LocalInfileHandler, LocalInfileReader},
     options::{ConnectionOptions, ConnectionOptionsTrait},
     prepared_statement::PreparedStatement,
     result_set::ResultSet,
     timeout::{Timeout, TimeoutFuture},
+    transaction::{AccessMode, IsolationLevel, Transaction, TransactionOptions},
 };
 
+#[cfg(feature = "tls")]
+#[cfg_attr(doc, doc(cfg(feature = "tls")))]
+pub use tls::{TlsOptions, TlsVerifyMode};
+
 pub struct Connection {
     stream: Box<dyn StreamRequirements>,
     seq_id: u8,
     data: ConnectionData,
     options: Arc<dyn ConnectionOptionsTrait>,
     pending_result: bool,
+    /// Rollback of a dropped [`Transaction`], executed before the next command.
+    pending_rollback: Option<usize>,
+    stmt_cache: stmt_cache::StmtCache,
+    /// Ids of dropped [`PreparedStatement`]s, closed before the next command.
+    pending_stmt_closes: Vec<u32>,
 }
 
 impl Connection {
@@ -91,6 +118,11 @@ pub trait Stream: Sized + StreamRequirements {
     type Options: Default + fmt::Debug + Send + Sync;
 
     async fn connect(data: &Self::Options) -> Result<Self, std::io::Error>;
+
+    /// Host name of the server, used to verify its certificate when upgrading to TLS.
+    fn host(_data: &Self::Options) -> Option<&str> {
+        None
+    }
 }
 
 impl AsyncPoolContentError for Connection {
@@ -139,4 +171,36 @@ impl Stream for tokio::net::TcpStream {
         this.set_nodelay(data.nodelay)?;
         Ok(this)
     }
+
+    fn host(data: &Self::Options) -> Option<&str> {
+        Some(&data.host)
+    }
+}
+
+#[cfg(all(unix, feature = "unix-socket"))]
+#[cfg_attr(doc, doc(cfg(all(unix, feature = "unix-socket"))))]
+#[derive(Debug)]
+pub struct UnixStreamOptions {
+    pub path: std::path::PathBuf,
+}
+
+#[cfg(all(unix, feature = "unix-socket"))]
+#[cfg_attr(doc, doc(cfg(all(unix, feature = "unix-socket"))))]
+impl Default for UnixStreamOptions {
+    fn default() -> Self {
+        Self {
+            path: std::path::PathBuf::from("/var/run/mysqld/mysqld.sock"),
+        }
+    }
+}
+
+#[cfg(all(unix, feature = "unix-socket"))]
+#[cfg_attr(doc, doc(cfg(all(unix, feature = "unix-socket"))))]
+impl Stream for tokio::net::UnixStream {
+    const SECURE: bool = true;
+    type Options = UnixStreamOptions;
+
+    async fn connect(data: &Self::Options) -> Result<Self, std::io::Error> {
+        Self::connect(&data.path).await
+    }
 }
@@ -1,7 +1,8 @@
 use {
     super::types::AuthPlugin,
-    crate::{bitflags::CapabilityFlags, Stream},
-    std::{fmt, time::Duration},
+    crate::{bitflags::CapabilityFlags, LocalInfileHandler, Stream},
+    chrono::FixedOffset,
+    std::{fmt, sync::Arc, time::Duration},
 };
 
 pub trait ConnectionOptionsTrait: fmt::Debug + Send + Sync {
@@ -12,9 +13,15 @@ pub trait ConnectionOptionsTrait: fmt::Debug + Send + Sync {
     fn timeout(&self) -> Duration;
     fn allow_cleartext_password(&self) -> bool;
     fn auth_plugin(&self) -> Option<AuthPlugin>;
+    fn local_infile(&self) -> Option<&Arc<dyn LocalInfileHandler>>;
+    fn stmt_cache_size(&self) -> usize;
+    fn time_zone(&self) -> Option<FixedOffset>;
     #[cfg(feature = "caching-sha2-password")]
     #[cfg_attr(doc, doc(cfg(feature = "caching-sha2-password")))]
     fn server_key(&self) -> Option<std::sync::Arc<crate::PublicKey>>;
+    #[cfg(feature = "tls")]
+    #[cfg_attr(doc, doc(cfg(feature = "tls")))]
+    fn tls(&self) -> Option<&crate::TlsOptions>;
     #[cfg(not(feature = "time"))]
     #[cfg_attr(doc, doc(cfg(not(feature = "time"))))]
     fn sleep(&self) -> Option<&'static (dyn Fn(Duration) -> crate::TimeoutFuture + Send + Sync)>;
@@ -31,9 +38,34 @@ pub struct ConnectionOptions<T: Stream> {
     pub allow_cleartext_password: bool,
     /// Ignore auth plugin specified in handshake and start authentication using this plugin.
     pub auth_plugin: Option<AuthPlugin>,
+    /// Allow multiple statements separated by `;` in a single query.
+    ///
+    /// Use [`ResultSet::next_result_set`](crate::ResultSet::next_result_set) to read the results of the following statements.
+    pub multi_statements: bool,
+    /// Handler for `LOAD DATA LOCAL INFILE`. The server is only allowed to request files if this is set.
+    pub local_infile: Option<Arc<dyn LocalInfileHandler>>,
+    /// Number of prepared statements cached per connection. `0` disables the cache.
+    pub stmt_cache_size: usize,
+    /// Allow the server to omit column definitions if the session variable `resultset_metadata` is set to `NONE`.
+    ///
+    /// Prepared statements then reuse the columns returned when preparing them.
+    pub optional_resultset_metadata: bool,
+    /// Session `time_zone`, which is set after connecting. The offset has to be whole minutes from
+    /// `-13:59` to `+14:00`, otherwise connecting fails with
+    /// [`RuntimeError::UnsupportedTimeZone`](crate::error::RuntimeError::UnsupportedTimeZone).
+    ///
+    /// The server returns `TIMESTAMP` values in this offset and stores `DATETIME` values as they are.
+    /// `DATETIME` and `TIMESTAMP` values of rows and prepared statement parameters are converted from
+    /// and to UTC, so [`Value::Datetime`](crate::types::Value::Datetime) and its conversions are
+    /// always UTC. Values formatted into query strings are sent as they are.
+    pub time_zone: Option<FixedOffset>,
     #[cfg(feature = "caching-sha2-password")]
     #[cfg_attr(doc, doc(cfg(feature = "caching-sha2-password")))]
     pub server_key: Option<std::sync::Arc<crate::PublicKey>>,
+    /// Upgrade the connection to TLS. Fails if the server doesn't support it.
+    #[cfg(feature = "tls")]
+    #[cfg_attr(doc, doc(cfg(feature = "tls")))]
+    pub tls: Option<crate::TlsOptions>,
     #[cfg(not(feature = "time"))]
     #[cfg_attr(doc, doc(cfg(not(feature = "time"))))]
     pub sleep: Option<&'static (dyn Fn(Duration) -> crate::TimeoutFuture + Send + Sync)>,
@@ -68,11 +100,28 @@ impl<T: Stream> ConnectionOptionsTrait for ConnectionOptions<T> {
         self.auth_plugin
     }
 
+    fn local_infile(&self) -> Option<&Arc<dyn LocalInfileHandler>> {
+        self.local_infile.as_ref()
+    }
+
+    fn stmt_cache_size(&self) -> usize {
+        self.stmt_cache_size
+    }
+
+    fn time_zone(&self) -> Option<FixedOffset> {
+        self.time_zone
+    }
+
     #[cfg(feature = "caching-sha2-password")]
     fn server_key(&self) -> Option<std::sync::Arc<crate::PublicKey>> {
         self.server_key.clone()
     }
 
+    #[cfg(feature = "tls")]
+    fn tls(&self) -> Option<&crate::TlsOptions> {
+        self.tls.as_ref()
+    }
+
     #[cfg(not(feature = "time"))]
     fn sleep(&self) -> Option<&'static (dyn Fn(Duration) -> crate::TimeoutFuture + Send + Sync)> {
         self.sleep
@@ -82,6 +131,7 @@ impl<T: Stream> ConnectionOptionsTrait for ConnectionOptions<T> {
         let mut out = CapabilityFlags::PROTOCOL_41
             | CapabilityFlags::SECURE_CONNECTION
             | CapabilityFlags::TRANSACTIONS
+            | CapabilityFlags::MULTI_RESULTS
             | CapabilityFlags::PS_MULTI_RESULTS
             | CapabilityFlags::DEPRECATE_EOF
             | CapabilityFlags::PLUGIN_AUTH;
@@ -90,6 +140,23 @@ impl<T: Stream> ConnectionOptionsTrait for ConnectionOptions<T> {
             out |= CapabilityFlags::CONNECT_WITH_DB;
         }
 
+        if self.multi_statements {
+            out |= CapabilityFlags::MULTI_STATEMENTS;
+        }
+
+        if self.local_infile.is_some() {
+            out |= CapabilityFlags::LOCAL_FILES;
+        }
+
+        if self.optional_resultset_metadata {
+            out |= CapabilityFlags::OPTIONAL_RESULTSET_METADATA;
+        }
+
+        #[cfg(feature = "tls")]
+        if self.tls.is_some() {
+            out |= CapabilityFlags::SSL;
+        }
+
         out
     }
 }
@@ -108,8 +175,15 @@ impl<T: Stream> Default for ConnectionOptions<T> {
             auth_plugin: Some(AuthPlugin::Sha2),
             #[cfg(not(feature = "caching-sha2-password"))]
             auth_plugin: None,
+            multi_statements: false,
+            local_infile: None,
+            stmt_cache_size: 32,
+            optional_resultset_metadata: false,
+            time_zone: None,
             #[cfg(feature = "caching-sha2-password")]
             server_key: None,
+            #[cfg(feature = "tls")]
+            tls: None,
             #[cfg(not(feature = "time"))]
             sleep: None,
         }
@@ -127,9 +201,19 @@ impl<T: Stream> fmt::Debug for ConnectionOptions<T> {
             .field("max_allowed_packet", &self.max_allowed_packet)
             .field("timeout", &self.timeout)
             .field("allow_cleartext_password", &self.allow_cleartext_password)
-            .field("auth_plugin", &self.auth_plugin);
+            .field("auth_plugin", &self.auth_plugin)
+            .field("multi_statements", &self.multi_statements)
+            .field("local_infile", &self.local_infile)
+            .field("stmt_cache_size", &self.stmt_cache_size)
+            .field(
+                "optional_resultset_metadata",
+                &self.optional_resultset_metadata,
+            )
+            .field("time_zone", &self.time_zone);
         #[cfg(feature = "caching-sha2-password")]
         debug.field("server_key", &self.server_key);
+        #[cfg(feature = "tls")]
+        debug.field("tls", &self.tls);
         debug.finish()
     }
 }
@@ -1,6 +1,6 @@
 use {
     crate::{
-        bitflags::{CapabilityFlags, StatusFlags},
+        bitflags::{CapabilityFlags, MariadbCapabilityFlags, StatusFlags},
         connection::types::{AuthPlugin, HalfInteger},
         error::ProtocolError,
         Deserialize, ParseBuf,
@@ -14,6 +14,7 @@ pub struct HandshakePacket<'a> {
     server_version: &'a str,
     connection_id: u32,
     capabilities: CapabilityFlags,
+    mariadb_capabilities: MariadbCapabilityFlags,
     default_collation: u8,
     status_flags: StatusFlags,
     auth_plugin_data_len: u8,
@@ -39,9 +40,16 @@ impl<'de> Deserialize<'de> for HandshakePacket<'de> {
         let status_flags = buf.parse_unchecked(())?;
         let capabilities_bits_2 = <u32 as HalfInteger>::deserialize_upper(buf)?;
         let auth_plugin_data_len: u8 = buf.parse_unchecked(())?;
-        buf.skip(10);
+        buf.skip(6);
+        let mariadb_capabilities_bits: u32 = buf.parse_unchecked(())?;
 
         let capabilities = CapabilityFlags::try_from(capabilities_bits_1 | capabilities_bits_2)?;
+        // MariaDB reuses the end of the filler, which is signaled by the missing `CLIENT_MYSQL` bit
+        let mariadb_capabilities = if capabilities.contains(CapabilityFlags::LONG_PASSWORD) {
+            MariadbCapabilityFlags::empty()
+        } else {
+            MariadbCapabilityFlags::from_bits_truncate(mariadb_capabilities_bits)
+        };
 
         if capabilities.contains(CapabilityFlags::SECURE_CONNECTION) {
             let len = i8::max(13, auth_plugin_data_len as i8 - 8) as usize;
@@ -63,6 +71,7 @@ impl<'de> Deserialize<'de> for HandshakePacket<'de> {
             server_version,
             connection_id,
             capabilities,
+            mariadb_capabilities,
             default_collation,
             status_flags,
             auth_plugin_data_len,
@@ -123,6 +132,10 @@ impl<'a> HandshakePacket<'a> {
         self.capabilities
     }
 
+    pub fn mariadb_capabilities(&self) -> MariadbCapabilityFlags {
+        self.mariadb_capabilities
+    }
+
     pub fn default_collation(&self) -> u8 {
         self.default_collation
     }
@@ -1,6 +1,6 @@
 use {
     crate::{
-        bitflags::CapabilityFlags,
+        bitflags::{CapabilityFlags, MariadbCapabilityFlags},
         connection::{types::AuthPlugin, UTF8MB4_GENERAL_CI, UTF8_GENERAL_CI},
         utils::{lenenc_slice_len, BufMutExt},
         Serialize,
@@ -12,6 +12,7 @@ use {
 #[derive(Debug, Clone)]
 pub struct HandshakeResponse<'a> {
     capabilities: CapabilityFlags,
+    mariadb_capabilities: MariadbCapabilityFlags,
     max_packet_size: u32,
     collation: u8,
     scramble: &'a [u8],
@@ -34,7 +35,8 @@ impl Serialize for HandshakeResponse<'_> {
         self.capabilities.serialize(buf);
         self.max_packet_size.serialize(buf);
         self.collation.serialize(buf);
-        buf.put_slice(&[0; 23]);
+        buf.put_slice(&[0; 19]);
+        self.mariadb_capabilities.serialize(buf);
         buf.put_null_slice(self.user);
 
         match self.scramble_encoding {
@@ -116,8 +118,36 @@ impl<'a> HandshakeResponse<'a> {
             db_name,
             auth_plugin,
             capabilities,
+            mariadb_capabilities: MariadbCapabilityFlags::empty(),
             connect_attributes,
             max_packet_size,
         }
     }
 }
+
+impl HandshakeResponse<'_> {
+    /// Sets the MariaDB extended capabilities. They must be empty if the server isn't MariaDB.
+    pub fn with_mariadb_capabilities(mut self, capabilities: MariadbCapabilityFlags) -> Self {
+        self.mariadb_capabilities = capabilities;
+        self
+    }
+}
+
+#[cfg(feature = "tls")]
+impl HandshakeResponse<'_> {
+    pub fn capabilities(&self) -> CapabilityFlags {
+        self.capabilities
+    }
+
+    pub fn mariadb_capabilities(&self) -> MariadbCapabilityFlags {
+        self.mariadb_capabilities
+    }
+
+    pub fn max_packet_size(&self) -> u32 {
+        self.max_packet_size
+    }
+
+    pub fn collation(&self) -> u8 {
+        self.collation
+    }
+}
@@ -4,15 +4,24 @@ mod err;
 mod handshake;
 mod handshake_response;
 mod ok;
+#[cfg(feature = "tls")]
+mod ssl_request;
 mod stmt;
+mod stmt_bulk_execute;
 mod stmt_close;
 mod stmt_execute_request;
+mod stmt_fetch;
+mod stmt_reset;
 mod stmt_send_long_data;
 
 #[allow(unused_imports)]
 pub(crate) use {
     auth_switch_request::AuthSwitchRequest, column_def::ColumnDef, err::ErrPacket,
     handshake::HandshakePacket, handshake_response::HandshakeResponse, ok::OkPacket, stmt::Stmt,
-    stmt_close::StmtClose, stmt_execute_request::StmtExecuteRequest,
+    stmt_bulk_execute::StmtBulkExecute, stmt_close::StmtClose,
+    stmt_execute_request::StmtExecuteRequest, stmt_fetch::StmtFetch, stmt_reset::StmtReset,
     stmt_send_long_data::StmtSendLongData,
 };
+
+#[cfg(feature = "tls")]
+pub(crate) use ssl_request::SslRequest;
@@ -87,6 +87,7 @@ impl OkPacket {
         // because valid content of EOF packet includes
         // packet marker, server status and warning count only.
         // (see `read_ok_ex` in sql-common/client.cc)
+        buf.checked_eat_u8()?; // packet marker (0xFE)
         let _ = buf.checked_eat_lenenc_int();
         let _ = buf.checked_eat_lenenc_int();
 
@@ -0,0 +1,69 @@
+use {
+    super::HandshakeResponse,
+    crate::{
+        bitflags::{CapabilityFlags, MariadbCapabilityFlags},
+        Serialize,
+    },
+    bytes::BufMut,
+};
+
+/// Sent instead of the [`HandshakeResponse`] to ask the server to switch to TLS.
+///
+/// It contains the first part of the handshake response, which is repeated after the TLS handshake.
+#[derive(Debug, Clone)]
+pub struct SslRequest {
+    capabilities: CapabilityFlags,
+    mariadb_capabilities: MariadbCapabilityFlags,
+    max_packet_size: u32,
+    collation: u8,
+}
+
+impl From<&HandshakeResponse<'_>> for SslRequest {
+    fn from(value: &HandshakeResponse<'_>) -> Self {
+        Self {
+            capabilities: value.capabilities(),
+            mariadb_capabilities: value.mariadb_capabilities(),
+            max_packet_size: value.max_packet_size(),
+            collation: value.collation(),
+        }
+    }
+}
+
+impl Serialize for SslRequest {
+    fn serialize(&self, buf: &mut Vec<u8>) {
+        self.capabilities.serialize(buf);
+        self.max_packet_size.serialize(buf);
+        self.collation.serialize(buf);
+        buf.put_slice(&[0; 19]);
+        self.mariadb_capabilities.serialize(buf);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::SslRequest,
+        crate::{bitflags::CapabilityFlags, packets::HandshakeResponse, Serialize},
+    };
+
+    #[test]
+    fn prefix_of_handshake_response() {
+        let response = HandshakeResponse::new(
+            &[1, 2, 3],
+            (8, 0, 0),
+            b"user",
+            Some(b"db"),
+            None,
+            CapabilityFlags::PROTOCOL_41 | CapabilityFlags::SSL,
+            None,
+            1024,
+        );
+        let mut request = Vec::new();
+        SslRequest::from(&response).serialize(&mut request);
+        let mut full = Vec::new();
+        response.serialize(&mut full);
+
+        assert_eq!(request.len(), 32);
+        assert_eq!(request[..], full[..32]);
+    }
+}
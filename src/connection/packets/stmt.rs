@@ -1,4 +1,4 @@
-use crate::{error::ProtocolError, Deserialize, ParseBuf};
+use crate::{bitflags::CapabilityFlags, error::ProtocolError, Deserialize, ParseBuf};
 
 #[derive(Debug)]
 #[allow(dead_code)]
@@ -7,13 +7,15 @@ pub struct Stmt {
     pub columns_len: u16,
     pub params_len: u16,
     pub warning_count: u16,
+    /// `false` if the server omitted the parameter and column definitions.
+    pub metadata_follows: bool,
 }
 
 impl Deserialize<'_> for Stmt {
-    const SIZE: Option<usize> = Some(12);
-    type Ctx = ();
+    const SIZE: Option<usize> = None;
+    type Ctx = CapabilityFlags;
 
-    fn deserialize(buf: &mut ParseBuf<'_>, _ctx: Self::Ctx) -> Result<Self, ProtocolError> {
+    fn deserialize(buf: &mut ParseBuf<'_>, ctx: Self::Ctx) -> Result<Self, ProtocolError> {
         buf.check_len(12)?;
         buf.skip(1);
         Ok(Self {
@@ -24,6 +26,11 @@ impl Deserialize<'_> for Stmt {
                 buf.skip(1);
                 buf.eat_u16()
             },
+            metadata_follows: if ctx.contains(CapabilityFlags::OPTIONAL_RESULTSET_METADATA) {
+                buf.checked_eat_u8()? != 0
+            } else {
+                true
+            },
         })
     }
 }
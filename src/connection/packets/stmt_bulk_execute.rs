@@ -0,0 +1,155 @@
+use {
+    crate::{
+        bitflags::{StmtBulkFlags, StmtExecuteParamFlags},
+        connection::types::ColumnType,
+        types::{SimpleValue, Value},
+        Command, Serialize,
+    },
+    bytes::BufMut,
+};
+
+const INDICATOR_NONE: u8 = 0;
+const INDICATOR_NULL: u8 = 1;
+
+/// MariaDB's `COM_STMT_BULK_EXECUTE`, which executes a statement once for every row.
+#[derive(Debug)]
+pub struct StmtBulkExecute<'a, V: SimpleValue> {
+    stmt_id: u32,
+    flags: StmtBulkFlags,
+    types: &'a [(ColumnType, StmtExecuteParamFlags)],
+    rows: &'a [&'a [V]],
+}
+
+impl<'a, V: SimpleValue> StmtBulkExecute<'a, V> {
+    pub fn new(
+        stmt_id: u32,
+        types: &'a [(ColumnType, StmtExecuteParamFlags)],
+        rows: &'a [&'a [V]],
+    ) -> Self {
+        Self {
+            stmt_id,
+            flags: StmtBulkFlags::SEND_TYPES_TO_SERVER,
+            types,
+            rows,
+        }
+    }
+
+    /// Types of the parameters, which are sent once for all rows.
+    ///
+    /// Returns `None` if the non-null values of a parameter differ in type.
+    pub fn param_types(
+        params_len: usize,
+        rows: &[&[V]],
+    ) -> Option<Vec<(ColumnType, StmtExecuteParamFlags)>> {
+        let mut types = vec![None; params_len];
+        for row in rows {
+            for (ty, param) in types.iter_mut().zip(row.iter()) {
+                let value = param.value();
+                if let Value::Null = value {
+                    continue;
+                }
+                let flags = if value.is_unsigned() {
+                    StmtExecuteParamFlags::UNSIGNED
+                } else {
+                    StmtExecuteParamFlags::empty()
+                };
+                match ty {
+                    Some(x) if *x != (value.column_type(), flags) => return None,
+                    Some(_) => {}
+                    None => *ty = Some((value.column_type(), flags)),
+                }
+            }
+        }
+        Some(
+            types
+                .into_iter()
+                .map(|x| x.unwrap_or((ColumnType::Null, StmtExecuteParamFlags::empty())))
+                .collect(),
+        )
+    }
+
+    /// Length of the packet without rows.
+    pub fn header_len(params_len: usize) -> usize {
+        7 + 2 * params_len
+    }
+
+    /// Length of a row in the packet.
+    pub fn row_len(row: &[V]) -> usize {
+        row.iter().map(|x| 1 + x.value().bin_len() as usize).sum()
+    }
+}
+
+impl<V: SimpleValue> Serialize for StmtBulkExecute<'_, V> {
+    fn serialize(&self, buf: &mut Vec<u8>) {
+        (Command::StmtBulkExecute as u8).serialize(&mut *buf);
+        self.stmt_id.serialize(&mut *buf);
+        self.flags.serialize(&mut *buf);
+
+        for (column_type, flags) in self.types {
+            buf.put_slice(&[*column_type as u8, flags.bits()]);
+        }
+
+        for row in self.rows {
+            for param in row.iter() {
+                match param.value() {
+                    Value::Null => buf.put_u8(INDICATOR_NULL),
+                    value => {
+                        buf.put_u8(INDICATOR_NONE);
+                        value.serialize(buf);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::StmtBulkExecute,
+        crate::{
+            bitflags::StmtExecuteParamFlags, connection::types::ColumnType, types::Value, Serialize,
+        },
+    };
+
+    #[test]
+    fn param_types() {
+        let rows: [&[Value]; 3] = [
+            &[Value::Null, Value::UInt(1)],
+            &[Value::Int(2), Value::UInt(3)],
+            &[Value::Null, Value::Null],
+        ];
+        assert_eq!(
+            StmtBulkExecute::param_types(2, &rows),
+            Some(vec![
+                (ColumnType::Long, StmtExecuteParamFlags::empty()),
+                (ColumnType::Long, StmtExecuteParamFlags::UNSIGNED),
+            ])
+        );
+
+        let rows: [&[Value]; 2] = [&[Value::Int(1)], &[Value::Long(2)]];
+        assert_eq!(StmtBulkExecute::param_types(1, &rows), None);
+    }
+
+    #[test]
+    fn serialize() {
+        let rows: [&[Value]; 2] = [&[Value::Tiny(1)], &[Value::Null]];
+        let types = StmtBulkExecute::param_types(1, &rows).unwrap();
+        let packet = StmtBulkExecute::new(7, &types, &rows);
+        let mut buf = Vec::new();
+        packet.serialize(&mut buf);
+
+        assert_eq!(
+            buf,
+            [0xFA, 7, 0, 0, 0, 128, 0, ColumnType::Tiny as u8, 0, 0, 1, 1]
+        );
+        assert_eq!(
+            buf.len(),
+            StmtBulkExecute::<Value>::header_len(1)
+                + rows
+                    .iter()
+                    .map(|x| StmtBulkExecute::row_len(x))
+                    .sum::<usize>()
+        );
+    }
+}
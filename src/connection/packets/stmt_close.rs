@@ -6,7 +6,6 @@ pub struct StmtClose {
 }
 
 impl StmtClose {
-    #[allow(dead_code)]
     pub fn new(stmt_id: u32) -> Self {
         Self { stmt_id }
     }
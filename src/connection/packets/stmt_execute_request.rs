@@ -46,6 +46,11 @@ impl<'a, V: SimpleValue> StmtExecuteRequest<'a, V> {
         }
     }
 
+    pub fn with_cursor_type(mut self, flags: CursorTypeFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
     pub fn as_long_data(&self) -> bool {
         self.as_long_data
     }
@@ -0,0 +1,21 @@
+use crate::{Command, Serialize};
+
+#[derive(Debug)]
+pub struct StmtFetch {
+    stmt_id: u32,
+    rows: u32,
+}
+
+impl StmtFetch {
+    pub fn new(stmt_id: u32, rows: u32) -> Self {
+        Self { stmt_id, rows }
+    }
+}
+
+impl Serialize for StmtFetch {
+    fn serialize(&self, buf: &mut Vec<u8>) {
+        (Command::StmtFetch as u8).serialize(buf);
+        self.stmt_id.serialize(buf);
+        self.rows.serialize(buf);
+    }
+}
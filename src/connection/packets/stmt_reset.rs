@@ -0,0 +1,19 @@
+use crate::{Command, Serialize};
+
+#[derive(Debug)]
+pub struct StmtReset {
+    stmt_id: u32,
+}
+
+impl StmtReset {
+    pub fn new(stmt_id: u32) -> Self {
+        Self { stmt_id }
+    }
+}
+
+impl Serialize for StmtReset {
+    fn serialize(&self, buf: &mut Vec<u8>) {
+        (Command::StmtReset as u8).serialize(buf);
+        self.stmt_id.serialize(buf);
+    }
+}
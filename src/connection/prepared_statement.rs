@@ -1,52 +1,242 @@
 use {
-    super::{types::BinaryProtocol, Command, Connection, ParseBuf, ResultSet},
+    super::{
+        types::{BinaryProtocol, Column},
+        BatchResult, Command, Connection, Cursor, ParseBuf, ResultSet,
+    },
     crate::{
+        bitflags::{CursorTypeFlags, StatusFlags},
         error::{ProtocolError, RuntimeError},
         model::FromQueryResult,
-        packets::{ErrPacket, OkPacket, Stmt, StmtExecuteRequest},
+        packets::{ErrPacket, OkPacket, Stmt, StmtClose, StmtExecuteRequest, StmtReset},
         types::SimpleValue,
         Deserialize, Error,
     },
+    std::sync::Arc,
 };
 
+#[derive(Debug, Clone)]
+pub(super) struct StmtMetadata {
+    pub id: u32,
+    pub params_len: usize,
+    pub params: Arc<[Column]>,
+    pub columns: Arc<[Column]>,
+}
+
+/// A prepared statement.
+///
+/// Statements that are not in the statement cache are closed when dropped.
+/// As this can't be done in `drop`, they are closed before the next command.
 #[derive(Debug)]
 pub struct PreparedStatement<'a> {
-    id: u32,
+    metadata: StmtMetadata,
     conn: &'a mut Connection,
-    params: usize,
+    closed: bool,
 }
 
 impl<'a> PreparedStatement<'a> {
+    pub fn id(&self) -> u32 {
+        self.metadata.id
+    }
+
+    /// Definitions of the parameters.
+    ///
+    /// This is empty if the server omitted the metadata (see
+    /// [`ConnectionOptions::optional_resultset_metadata`](crate::ConnectionOptions::optional_resultset_metadata)).
+    pub fn params(&self) -> &[Column] {
+        &self.metadata.params
+    }
+
+    /// Columns of the result set.
+    ///
+    /// This is empty if the statement doesn't return rows, or if the server omitted the metadata.
+    pub fn columns(&self) -> &[Column] {
+        &self.metadata.columns
+    }
+
     pub async fn query<V: SimpleValue, R: FromQueryResult>(
         &mut self,
         values: &[V],
     ) -> Result<ResultSet<'_, BinaryProtocol, R>, Error> {
-        if values.len() != self.params {
+        if values.len() != self.metadata.params_len {
             return Err(RuntimeError::ParameterCountMismatch.into());
         }
 
-        self.conn
-            .query_prepared_statement_unchecked(self.id, values)
-            .await
+        let (id, columns) = (self.metadata.id, self.metadata.columns.clone());
+        match self.conn.params_from_utc(values) {
+            Some(values) => {
+                self.conn
+                    .query_prepared_statement_unchecked(id, &values, columns)
+                    .await
+            }
+            None => {
+                self.conn
+                    .query_prepared_statement_unchecked(id, values, columns)
+                    .await
+            }
+        }
+    }
+
+    /// Executes the statement with a read-only server-side cursor.
+    ///
+    /// The rows are fetched in batches of `fetch_size` rows, so the result doesn't have to be buffered.
+    pub async fn query_cursor<V: SimpleValue, R: FromQueryResult>(
+        &mut self,
+        values: &[V],
+        fetch_size: u32,
+    ) -> Result<Cursor<'_, R>, Error> {
+        if values.len() != self.metadata.params_len {
+            return Err(RuntimeError::ParameterCountMismatch.into());
+        }
+
+        let id = self.metadata.id;
+        match self.conn.params_from_utc(values) {
+            Some(values) => self.conn.write_cursor_execute(id, &values).await?,
+            None => self.conn.write_cursor_execute(id, values).await?,
+        }
+
+        let columns = match self
+            .conn
+            .read_result_set_header(Some(self.metadata.columns.clone()))
+            .await?
+        {
+            Ok(columns) => columns,
+            // no rows
+            Err(_) => return Ok(Cursor::new(self.conn, id, fetch_size, Vec::new(), true)),
+        };
+        // the column definitions are followed by the status, rows are only sent on fetch
+        let packet = self.conn.read_packet().await?;
+        if !self.conn.is_last_result_set_packet(&packet) {
+            return Err(
+                ProtocolError::unexpected_packet(packet.to_vec(), Some("EOF Packet")).into(),
+            );
+        }
+        let status = self.conn.read_result_set_end(packet)?.status();
+        let finished = !status.contains(StatusFlags::CURSOR_EXISTS);
+        Ok(Cursor::new(self.conn, id, fetch_size, columns, finished))
     }
 
     pub async fn execute<V: SimpleValue>(&mut self, values: &[V]) -> Result<OkPacket, Error> {
-        if values.len() != self.params {
+        if values.len() != self.metadata.params_len {
             return Err(RuntimeError::ParameterCountMismatch.into());
         }
 
+        let id = self.metadata.id;
+        match self.conn.params_from_utc(values) {
+            Some(values) => {
+                self.conn
+                    .execute_prepared_statement_unchecked(id, &values)
+                    .await
+            }
+            None => {
+                self.conn
+                    .execute_prepared_statement_unchecked(id, values)
+                    .await
+            }
+        }
+    }
+
+    /// Executes the statement once for every row.
+    ///
+    /// The executions are pipelined, or sent with `COM_STMT_BULK_EXECUTE` if the server is MariaDB
+    /// and supports it. If an execution fails, the first error is returned, but the rows
+    /// that were already sent are executed anyway.
+    pub async fn execute_batch<'v, V: SimpleValue + 'v>(
+        &mut self,
+        rows: impl IntoIterator<Item = &'v [V]>,
+    ) -> Result<BatchResult, Error> {
+        let rows = rows.into_iter().collect::<Vec<_>>();
+        if rows.iter().any(|x| x.len() != self.metadata.params_len) {
+            return Err(RuntimeError::ParameterCountMismatch.into());
+        }
+
+        let (id, params_len) = (self.metadata.id, self.metadata.params_len);
+        match self.conn.rows_from_utc(&rows) {
+            Some(rows) => {
+                let rows = rows.iter().map(Vec::as_slice).collect::<Vec<_>>();
+                self.conn
+                    .execute_batch_unchecked(id, params_len, &rows)
+                    .await
+            }
+            None => {
+                self.conn
+                    .execute_batch_unchecked(id, params_len, &rows)
+                    .await
+            }
+        }
+    }
+
+    /// Resets the data sent by long data packets and closes open cursors.
+    pub async fn reset(&mut self) -> Result<(), Error> {
         self.conn
-            .execute_prepared_statement_unchecked(self.id, values)
+            .write_command(&StmtReset::new(self.metadata.id))
+            .await?;
+        self.conn.read_response().await??;
+        Ok(())
+    }
+
+    /// Closes the statement and removes it from the statement cache.
+    pub async fn close(mut self) -> Result<(), Error> {
+        self.closed = true;
+        self.conn.stmt_cache.remove_id(self.metadata.id);
+        self.conn
+            .write_command(&StmtClose::new(self.metadata.id))
             .await
     }
 }
 
+impl Drop for PreparedStatement<'_> {
+    fn drop(&mut self) {
+        if !self.closed && !self.conn.stmt_cache.contains_id(self.metadata.id) {
+            self.conn.pending_stmt_closes.push(self.metadata.id);
+        }
+    }
+}
+
 impl Connection {
-    pub async fn prepare_statement(&mut self, stmt: &str) -> Result<PreparedStatement, Error> {
-        self.execute_command(Command::StmtPrepare, stmt).await?;
+    /// Prepares a statement or reuses it from the statement cache.
+    ///
+    /// The cache size is set by [`ConnectionOptions::stmt_cache_size`](crate::ConnectionOptions::stmt_cache_size).
+    pub async fn prepare_statement(&mut self, query: &str) -> Result<PreparedStatement<'_>, Error> {
+        if let Some(metadata) = self.stmt_cache.get(query) {
+            return Ok(PreparedStatement {
+                metadata,
+                conn: self,
+                closed: false,
+            });
+        }
+
+        let metadata = self.prepare(query).await?;
+        let capacity = self.options.stmt_cache_size();
+        for id in self.stmt_cache.insert(query, metadata.clone(), capacity) {
+            self.write_command(&StmtClose::new(id)).await?;
+        }
+
+        Ok(PreparedStatement {
+            metadata,
+            conn: self,
+            closed: false,
+        })
+    }
+
+    /// Prepares a statement without the statement cache, for generated statements which are
+    /// unlikely to be reused and would only evict the cached ones.
+    pub(crate) async fn prepare_statement_uncached(
+        &mut self,
+        query: &str,
+    ) -> Result<PreparedStatement<'_>, Error> {
+        let metadata = self.prepare(query).await?;
+        Ok(PreparedStatement {
+            metadata,
+            conn: self,
+            closed: false,
+        })
+    }
+
+    async fn prepare(&mut self, query: &str) -> Result<StmtMetadata, Error> {
+        self.execute_command(Command::StmtPrepare, query).await?;
         let packet = self.read_packet().await?;
         let stmt = match packet.first() {
-            Some(0x00) => Stmt::deserialize(&mut ParseBuf(&packet), ())?,
+            Some(0x00) => Stmt::deserialize(&mut ParseBuf(&packet), self.data.capabilities)?,
             Some(0xFF) => {
                 return Err(
                     ErrPacket::deserialize(&mut ParseBuf(&packet), self.data.capabilities)?.into(),
@@ -55,21 +245,50 @@ impl Connection {
             _ => return Err(ProtocolError::unexpected_packet(Vec::clone(&packet), None).into()),
         };
 
-        for _ in 0..(stmt.params_len as usize + stmt.columns_len as usize) {
-            self.read_packet().await?;
-        }
-
-        Ok(PreparedStatement {
+        let (params, columns) = if stmt.metadata_follows {
+            (
+                self.read_column_defs(stmt.params_len as usize).await?,
+                self.read_column_defs(stmt.columns_len as usize).await?,
+            )
+        } else {
+            (Vec::new(), Vec::new())
+        };
+        Ok(StmtMetadata {
             id: stmt.id,
-            conn: self,
-            params: stmt.params_len as usize,
+            params_len: stmt.params_len as usize,
+            params: params.into(),
+            columns: columns.into(),
         })
     }
 
+    /// Closes the statements of dropped [`PreparedStatement`]s.
+    pub(super) async fn close_pending_stmts(&mut self) -> Result<(), Error> {
+        while let Some(id) = self.pending_stmt_closes.pop() {
+            self.seq_id = 0;
+            self.write_struct(&StmtClose::new(id)).await?;
+        }
+        Ok(())
+    }
+
+    /// Executes the statement with a read-only cursor, whose rows are fetched by the [`Cursor`].
+    async fn write_cursor_execute<V: SimpleValue>(
+        &mut self,
+        id: u32,
+        params: &[V],
+    ) -> Result<(), Error> {
+        let request =
+            StmtExecuteRequest::new(id, params).with_cursor_type(CursorTypeFlags::READ_ONLY);
+        if request.as_long_data() {
+            self.send_long_data(id, params.iter()).await?;
+        }
+        self.write_command(&request).await
+    }
+
     async fn query_prepared_statement_unchecked<V: SimpleValue, R: FromQueryResult>(
         &mut self,
         id: u32,
         params: &[V],
+        columns: Arc<[Column]>,
     ) -> Result<ResultSet<'_, BinaryProtocol, R>, Error> {
         let request = StmtExecuteRequest::new(id, params);
 
@@ -78,7 +297,7 @@ impl Connection {
         }
 
         self.write_command(&request).await?;
-        ResultSet::read(self).await
+        ResultSet::read(self, Some(columns)).await
     }
 
     async fn execute_prepared_statement_unchecked<V: SimpleValue>(
@@ -4,6 +4,7 @@ use {
         Command, Connection, ParseBuf, ResultSet, BUFFER_POOL,
     },
     crate::{
+        error::RuntimeError,
         model::FromQueryResult,
         packets::{ColumnDef, OkPacket},
         types::Value,
@@ -17,15 +18,31 @@ impl Connection {
         query: &str,
     ) -> Result<ResultSet<'_, TextProtocol, R>, Error> {
         self.execute_command(Command::Query, query).await?;
-        ResultSet::read(self).await
+        ResultSet::read(self, None).await
     }
 
     pub async fn execute_query(&mut self, query: &str) -> Result<OkPacket, Error> {
         self.execute_command(Command::Query, query).await?;
-        self.read_response().await?.map_err(Into::into)
+        match self.read_result_set_header(None).await {
+            Ok(Err(ok_packet)) => Ok(ok_packet),
+            // the rows stay pending and are skipped before the next command
+            Err(Error::Runtime(RuntimeError::MissingResultSetMetadata)) | Ok(Ok(_)) => {
+                Err(RuntimeError::UnexpectedResultSet.into())
+            }
+            Err(err) => Err(err),
+        }
     }
 
     pub(super) async fn execute_command<D>(&mut self, cmd: Command, data: D) -> Result<(), Error>
+    where
+        D: AsRef<[u8]>,
+    {
+        self.cleanup().await?;
+        self.send_command(cmd, data).await
+    }
+
+    /// Sends a command without reading pending results first, see [`execute_command`](Self::execute_command).
+    pub(super) async fn send_command<D>(&mut self, cmd: Command, data: D) -> Result<(), Error>
     where
         D: AsRef<[u8]>,
     {
@@ -33,7 +50,6 @@ impl Connection {
         let body: &mut Vec<u8> = buf.as_mut();
         body.push(cmd as u8);
         body.extend_from_slice(data.as_ref());
-        self.cleanup().await?;
         self.seq_id = 0;
         self.write_packet(&buf).await
     }
@@ -48,6 +64,18 @@ impl Connection {
         Ok(columns)
     }
 
+    /// Sets the session `time_zone` of the options, which is also needed after resetting the connection.
+    pub(super) async fn set_time_zone(&mut self) -> Result<(), Error> {
+        if let Some(time_zone) = self.options.time_zone() {
+            // checked when connecting, so the offset is `+hh:mm` or `-hh:mm`
+            self.query::<Vec<Value>>(&format!("set time_zone = '{time_zone}'"))
+                .await?
+                .finish()
+                .await?;
+        }
+        Ok(())
+    }
+
     pub(super) async fn read_settings(&mut self) -> Result<(), Error> {
         if self.options.max_allowed_packet().is_none() {
             let mut res = self
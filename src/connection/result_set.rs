@@ -4,12 +4,14 @@ use {
         Connection, MAX_PAYLOAD_LEN,
     },
     crate::{
-        bitflags::CapabilityFlags,
+        bitflags::{CapabilityFlags, StatusFlags},
+        error::RuntimeError,
         model::{FromQueryResult, FromQueryResultMapping},
         packets::{ErrPacket, OkPacket},
+        pool::PoolItem,
         Deserialize, Error, ParseBuf,
     },
-    std::marker::PhantomData,
+    std::{marker::PhantomData, sync::Arc},
 };
 
 pub struct ResultSet<'a, P, R>
@@ -29,26 +31,18 @@ where
     P: Protocol,
     R: FromQueryResult,
 {
-    pub(super) async fn read(conn: &'a mut Connection) -> Result<Self, Error> {
-        let packet = conn.read_packet().await?;
-        match packet.first() {
-            Some(0x00) => {
+    /// Reads the result set. `columns` are used if the server omits the column definitions.
+    pub(super) async fn read(
+        conn: &'a mut Connection,
+        columns: Option<Arc<[Column]>>,
+    ) -> Result<Self, Error> {
+        match conn.read_result_set_header(columns).await? {
+            Ok(columns) => Ok(ResultSet::new(columns, conn)),
+            Err(ok_packet) => {
                 let mut res = ResultSet::new(Vec::new(), conn);
-                res.ok_packet = Some(res.conn.decode_response(&packet).await??);
+                res.ok_packet = Some(ok_packet);
                 Ok(res)
             }
-            Some(0xFB) => unimplemented!("local infile"),
-            Some(0xFF) => Err(ErrPacket::deserialize(
-                &mut ParseBuf(&packet),
-                conn.data().capabilities(),
-            )?
-            .into()),
-            _ => {
-                conn.pending_result = true;
-                let columns_len = ParseBuf(&packet).checked_eat_lenenc_int()?;
-                let columns = conn.read_column_defs(columns_len as usize).await?;
-                Ok(ResultSet::new(columns, conn))
-            }
         }
     }
 
@@ -68,22 +62,12 @@ where
             return Ok(None);
         }
         let packet = self.conn.read_packet().await?;
-        let is_last_result_set_packet = if self
-            .conn
-            .data
-            .capabilities
-            .contains(CapabilityFlags::DEPRECATE_EOF)
-        {
-            packet[0] == 0xFE && packet.len() < MAX_PAYLOAD_LEN
-        } else {
-            packet[0] == 0xFE && packet.len() < 8
-        };
-        if is_last_result_set_packet {
-            self.ok_packet = Some(OkPacket::read_eof(packet, self.conn.data.capabilities)?);
-            self.conn.pending_result = false;
+        if self.conn.is_last_result_set_packet(&packet) {
+            self.ok_packet = Some(self.conn.read_result_set_end(packet)?);
             Ok(None)
         } else {
             let mut row = P::read_result_set_row(&packet, &self.columns)?;
+            self.conn.row_to_utc(&mut row);
             Ok(Some(R::from_mapping_and_row(&self.mapping, &mut row)?))
         }
     }
@@ -122,6 +106,29 @@ where
         (self.columns, self.mapping)
     }
 
+    /// Returns `true` if another result set follows this one.
+    ///
+    /// This is only known after all rows of this result set were read.
+    pub fn has_next_result_set(&self) -> bool {
+        self.ok_packet
+            .as_ref()
+            .is_some_and(|x| x.status().contains(StatusFlags::MORE_RESULTS_EXISTS))
+    }
+
+    /// Skips the remaining rows and advances to the following result set.
+    ///
+    /// Returns `None` if this was the last result set, e.g. for multiple statements or stored procedures.
+    pub async fn next_result_set<R2: FromQueryResult>(
+        mut self,
+    ) -> Result<Option<ResultSet<'a, P, R2>>, Error> {
+        while self.next().await?.is_some() {}
+        if self.has_next_result_set() {
+            ResultSet::read(self.conn, None).await.map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
     pub async fn finish(mut self) -> Result<OkPacket, Error> {
         match self.ok_packet {
             Some(x) => Ok(x),
@@ -146,22 +153,116 @@ where
 }
 
 impl Connection {
+    /// Reads the rows of all pending result sets, executes pending rollbacks and closes dropped statements.
+    ///
+    /// Returns the last ok packet, or an error returned by the server for one of the pending result sets.
     pub async fn cleanup(&mut self) -> Result<Option<OkPacket>, Error> {
-        if self.pending_result {
-            loop {
-                let packet = self.read_packet().await?;
-                let is_last_result_set_packet = if self
+        let ok_packet = self.read_pending_results().await?;
+        if let Some(depth) = self.pending_rollback.take() {
+            self.execute_pending_rollback(depth).await?;
+        }
+        self.close_pending_stmts().await?;
+        Ok(ok_packet)
+    }
+
+    /// Reads the first packets of a result set.
+    ///
+    /// Returns the columns, or the ok packet if the statement doesn't return rows.
+    /// If `OPTIONAL_RESULTSET_METADATA` was negotiated and the server omits the column definitions,
+    /// `columns` are returned instead.
+    pub(super) async fn read_result_set_header(
+        &mut self,
+        columns: Option<Arc<[Column]>>,
+    ) -> Result<Result<Vec<Column>, OkPacket>, Error> {
+        let mut packet = self.read_packet().await?;
+        let mut local_infile_error = None;
+        if packet.first() == Some(&0xFB) {
+            local_infile_error = self.send_local_infile(&packet[1..]).await?;
+            packet = self.read_packet().await?;
+        }
+        match packet.first() {
+            Some(0x00) => {
+                let ok_packet = self.decode_response(&packet).await??;
+                match local_infile_error {
+                    Some(err) => Err(err),
+                    None => Ok(Err(ok_packet)),
+                }
+            }
+            Some(0xFF) => {
+                // an error ends the sequence of result sets
+                self.data.status.remove(StatusFlags::MORE_RESULTS_EXISTS);
+                let err = ErrPacket::deserialize(&mut ParseBuf(&packet), self.data.capabilities)?;
+                Err(local_infile_error.unwrap_or(err.into()))
+            }
+            _ => {
+                self.pending_result = true;
+                let mut buf = ParseBuf(&packet);
+                let columns_len = buf.checked_eat_lenenc_int()?;
+                let metadata_follows = !self
                     .data
                     .capabilities
-                    .contains(CapabilityFlags::DEPRECATE_EOF)
-                {
-                    packet[0] == 0xFE && packet.len() < MAX_PAYLOAD_LEN
+                    .contains(CapabilityFlags::OPTIONAL_RESULTSET_METADATA)
+                    || buf.checked_eat_u8()? != 0;
+                if metadata_follows {
+                    Ok(Ok(self.read_column_defs(columns_len as usize).await?))
                 } else {
-                    packet[0] == 0xFE && packet.len() < 8
-                };
-                if is_last_result_set_packet {
-                    self.pending_result = false;
-                    return Ok(Some(OkPacket::read_eof(packet, self.data.capabilities)?));
+                    match columns {
+                        Some(columns) if columns.len() as u64 == columns_len => {
+                            Ok(Ok(columns.to_vec()))
+                        }
+                        _ => Err(RuntimeError::MissingResultSetMetadata.into()),
+                    }
+                }
+            }
+        }
+    }
+
+    async fn read_pending_results(&mut self) -> Result<Option<OkPacket>, Error> {
+        let mut ok_packet = self.read_pending_rows().await?;
+        while self.data.status.contains(StatusFlags::MORE_RESULTS_EXISTS) {
+            ok_packet = match self.read_result_set_header(None).await {
+                // rows can be skipped without column definitions
+                Err(Error::Runtime(RuntimeError::MissingResultSetMetadata)) => {
+                    self.read_pending_rows().await?
+                }
+                res => match res? {
+                    Ok(_) => self.read_pending_rows().await?,
+                    Err(ok_packet) => Some(ok_packet),
+                },
+            };
+        }
+        Ok(ok_packet)
+    }
+
+    pub(super) fn is_last_result_set_packet(&self, packet: &[u8]) -> bool {
+        if self
+            .data
+            .capabilities
+            .contains(CapabilityFlags::DEPRECATE_EOF)
+        {
+            packet[0] == 0xFE && packet.len() < MAX_PAYLOAD_LEN
+        } else {
+            packet[0] == 0xFE && packet.len() < 8
+        }
+    }
+
+    /// Reads the packet ending the rows of a result set.
+    pub(super) fn read_result_set_end(
+        &mut self,
+        packet: PoolItem<'_, Vec<u8>>,
+    ) -> Result<OkPacket, Error> {
+        self.pending_result = false;
+        let ok_packet = OkPacket::read_eof(packet, self.data.capabilities)?;
+        self.data.status = ok_packet.status();
+        Ok(ok_packet)
+    }
+
+    async fn read_pending_rows(&mut self) -> Result<Option<OkPacket>, Error> {
+        if self.pending_result {
+            loop {
+                let packet = self.read_packet().await?;
+                if self.is_last_result_set_packet(&packet) {
+                    return self.read_result_set_end(packet).map(Some);
                 }
             }
         } else {
@@ -0,0 +1,97 @@
+use {super::prepared_statement::StmtMetadata, std::collections::HashMap};
+
+#[derive(Debug)]
+struct CachedStmt {
+    metadata: StmtMetadata,
+    last_used: u64,
+}
+
+/// Least recently used cache of prepared statements, keyed by the query.
+#[derive(Debug, Default)]
+pub(super) struct StmtCache {
+    stmts: HashMap<String, CachedStmt>,
+    tick: u64,
+}
+
+impl StmtCache {
+    pub fn get(&mut self, query: &str) -> Option<StmtMetadata> {
+        self.tick += 1;
+        let stmt = self.stmts.get_mut(query)?;
+        stmt.last_used = self.tick;
+        Some(stmt.metadata.clone())
+    }
+
+    /// Inserts a statement and returns the ids of the evicted statements, which have to be closed.
+    pub fn insert(&mut self, query: &str, metadata: StmtMetadata, capacity: usize) -> Vec<u32> {
+        let mut evicted = Vec::new();
+        if capacity == 0 {
+            return evicted;
+        }
+        while self.stmts.len() >= capacity {
+            let Some(query) = self
+                .stmts
+                .iter()
+                .min_by_key(|(_, stmt)| stmt.last_used)
+                .map(|(query, _)| query.clone())
+            else {
+                break;
+            };
+            evicted.extend(self.stmts.remove(&query).map(|x| x.metadata.id));
+        }
+        self.tick += 1;
+        self.stmts.insert(
+            query.to_owned(),
+            CachedStmt {
+                metadata,
+                last_used: self.tick,
+            },
+        );
+        evicted
+    }
+
+    pub fn contains_id(&self, id: u32) -> bool {
+        self.stmts.values().any(|x| x.metadata.id == id)
+    }
+
+    /// Removes the statement and returns `true` if it was cached.
+    pub fn remove_id(&mut self, id: u32) -> bool {
+        let len = self.stmts.len();
+        self.stmts.retain(|_, stmt| stmt.metadata.id != id);
+        self.stmts.len() != len
+    }
+
+    pub fn clear(&mut self) {
+        self.stmts.clear();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {super::StmtCache, crate::connection::prepared_statement::StmtMetadata};
+
+    fn stmt(id: u32) -> StmtMetadata {
+        StmtMetadata {
+            id,
+            params_len: 0,
+            params: Vec::new().into(),
+            columns: Vec::new().into(),
+        }
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let mut cache = StmtCache::default();
+        assert!(cache.insert("a", stmt(1), 2).is_empty());
+        assert!(cache.insert("b", stmt(2), 2).is_empty());
+        assert_eq!(cache.get("a").map(|x| x.id), Some(1));
+        assert_eq!(cache.insert("c", stmt(3), 2), [2]);
+        assert!(cache.get("b").is_none());
+        assert_eq!(cache.insert("d", stmt(4), 1), [1, 3]);
+        assert_eq!(cache.stmts.len(), 1);
+        assert!(cache.insert("e", stmt(5), 0).is_empty());
+        assert!(cache.get("e").is_none());
+        assert!(cache.remove_id(4));
+        assert!(!cache.remove_id(4));
+        assert!(cache.get("d").is_none());
+    }
+}
@@ -0,0 +1,1328 @@
+use {
+    super::{
+        types::{AuthPlugin, ColumnType},
+        Command, ConnectionOptions, UnixStreamOptions,
+    },
+    crate::{
+        self as mysql_connector,
+        bitflags::{
+            CapabilityFlags, ColumnFlags, CursorTypeFlags, MariadbCapabilityFlags, StatusFlags,
+        },
+        error::RuntimeError,
+        macros::*,
+        model::{ActiveModel, ActiveValue, HasActiveModel},
+        types::Value,
+        AccessMode, Connection, Error, IsolationLevel, LocalInfileHandler, LocalInfileReader,
+        StreamRequirements, TransactionOptions,
+    },
+    chrono::{DateTime, FixedOffset, TimeZone, Utc},
+    std::sync::Arc,
+    tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::{UnixListener, UnixStream},
+    },
+};
+
+/// Minimal stand-in for a MySQL server, speaking just enough of the protocol for the tests.
+struct Server {
+    stream: Box<dyn StreamRequirements>,
+    seq_id: u8,
+    /// Advertises [`CapabilityFlags::SSL`] in the handshake.
+    ssl: bool,
+}
+
+impl Server {
+    async fn accept(listener: &UnixListener) -> Self {
+        Self {
+            stream: Box::new(listener.accept().await.unwrap().0),
+            seq_id: 0,
+            ssl: false,
+        }
+    }
+
+    async fn read(&mut self) -> Vec<u8> {
+        let mut header = [0u8; 4];
+        self.stream.read_exact(&mut header).await.unwrap();
+        assert_eq!(header[3], self.seq_id, "sequence id");
+        self.seq_id = self.seq_id.wrapping_add(1);
+        let len = u32::from_le_bytes([header[0], header[1], header[2], 0]) as usize;
+        let mut packet = vec![0; len];
+        self.stream.read_exact(&mut packet).await.unwrap();
+        packet
+    }
+
+    async fn write(&mut self, packet: &[u8]) {
+        let mut header = (packet.len() as u32).to_le_bytes();
+        header[3] = self.seq_id;
+        self.seq_id = self.seq_id.wrapping_add(1);
+        self.stream.write_all(&header).await.unwrap();
+        self.stream.write_all(packet).await.unwrap();
+    }
+
+    async fn write_handshake(&mut self, auth_plugin: &str) {
+        self.write_handshake_as("8.4.0", MariadbCapabilityFlags::empty(), auth_plugin)
+            .await;
+    }
+
+    async fn write_handshake_as(
+        &mut self,
+        server_version: &str,
+        mariadb_capabilities: MariadbCapabilityFlags,
+        auth_plugin: &str,
+    ) {
+        let capabilities = (CapabilityFlags::PROTOCOL_41
+            | CapabilityFlags::SECURE_CONNECTION
+            | CapabilityFlags::TRANSACTIONS
+            | CapabilityFlags::LOCAL_FILES
+            | CapabilityFlags::MULTI_STATEMENTS
+            | CapabilityFlags::MULTI_RESULTS
+            | CapabilityFlags::PS_MULTI_RESULTS
+            | CapabilityFlags::DEPRECATE_EOF
+            | CapabilityFlags::OPTIONAL_RESULTSET_METADATA
+            | CapabilityFlags::PLUGIN_AUTH
+            | CapabilityFlags::CONNECT_WITH_DB
+            | match self.ssl {
+                true => CapabilityFlags::SSL,
+                false => CapabilityFlags::empty(),
+            })
+        .bits();
+        let mut packet = vec![10];
+        packet.extend_from_slice(server_version.as_bytes());
+        packet.push(0);
+        packet.extend_from_slice(&1u32.to_le_bytes());
+        packet.extend_from_slice(&[1; 8]);
+        packet.push(0);
+        packet.extend_from_slice(&(capabilities as u16).to_le_bytes());
+        packet.push(45);
+        packet.extend_from_slice(&0u16.to_le_bytes());
+        packet.extend_from_slice(&((capabilities >> 16) as u16).to_le_bytes());
+        packet.push(21);
+        packet.extend_from_slice(&[0; 6]);
+        packet.extend_from_slice(&mariadb_capabilities.bits().to_le_bytes());
+        packet.extend_from_slice(&[2; 12]);
+        packet.push(0);
+        packet.extend_from_slice(auth_plugin.as_bytes());
+        packet.push(0);
+        self.write(&packet).await;
+    }
+
+    async fn write_ok(&mut self) {
+        self.write_ok_with_status(StatusFlags::AUTOCOMMIT).await;
+    }
+
+    async fn write_ok_with_status(&mut self, status: StatusFlags) {
+        self.write_ok_with_ids(0, 0, status).await;
+    }
+
+    /// Writes an ok packet with affected rows and last insert id below 251.
+    async fn write_ok_with_ids(
+        &mut self,
+        affected_rows: u8,
+        last_insert_id: u8,
+        status: StatusFlags,
+    ) {
+        let mut packet = vec![0x00, affected_rows, last_insert_id];
+        packet.extend_from_slice(&status.bits().to_le_bytes());
+        packet.extend_from_slice(&[0x00, 0x00]);
+        self.write(&packet).await;
+    }
+
+    /// Writes a text result set with a single string column.
+    async fn write_result_set(&mut self, column: &str, rows: &[&str], status: StatusFlags) {
+        self.write(&[1]).await;
+        self.write_column_def(column).await;
+        for row in rows {
+            let mut packet = vec![row.len() as u8];
+            packet.extend_from_slice(row.as_bytes());
+            self.write(&packet).await;
+        }
+        self.write_eof(status).await;
+    }
+
+    /// Writes the definition of a string column.
+    async fn write_column_def(&mut self, column: &str) {
+        let mut def = vec![3];
+        def.extend_from_slice(b"def");
+        def.extend_from_slice(&[0, 0, 0, column.len() as u8]);
+        def.extend_from_slice(column.as_bytes());
+        def.extend_from_slice(&[0, 12, 45, 0, 255, 0, 0, 0, 0xFD, 0, 0, 0, 0, 0]);
+        self.write(&def).await;
+    }
+
+    /// Writes the definition of an unsigned `BIGINT` column of the table.
+    async fn write_bigint_column_def(&mut self, table: &str, column: &str) {
+        let mut def = vec![3];
+        def.extend_from_slice(b"def");
+        def.push(0);
+        for name in [table, table, column, column] {
+            def.push(name.len() as u8);
+            def.extend_from_slice(name.as_bytes());
+        }
+        def.extend_from_slice(&[12, 63, 0, 20, 0, 0, 0, ColumnType::LongLong as u8]);
+        def.extend_from_slice(&ColumnFlags::UNSIGNED_FLAG.bits().to_le_bytes());
+        def.extend_from_slice(&[0, 0, 0]);
+        self.write(&def).await;
+    }
+
+    /// Writes the definition of a `DATETIME` column.
+    async fn write_datetime_column_def(&mut self, column: &str) {
+        let mut def = vec![3];
+        def.extend_from_slice(b"def");
+        def.extend_from_slice(&[0, 0, 0, column.len() as u8]);
+        def.extend_from_slice(column.as_bytes());
+        def.extend_from_slice(&[0, 12, 63, 0, 19, 0, 0, 0, ColumnType::Datetime as u8]);
+        def.extend_from_slice(&ColumnFlags::BINARY_FLAG.bits().to_le_bytes());
+        def.extend_from_slice(&[0, 0, 0]);
+        self.write(&def).await;
+    }
+
+    async fn write_eof(&mut self, status: StatusFlags) {
+        let mut eof = vec![0xFE, 0x00, 0x00];
+        eof.extend_from_slice(&status.bits().to_le_bytes());
+        eof.extend_from_slice(&[0x00, 0x00]);
+        self.write(&eof).await;
+    }
+
+    /// Reads a `COM_STMT_PREPARE` and answers with a statement without parameters or columns.
+    async fn expect_prepare(&mut self, query: &str, id: u32) {
+        self.expect_prepare_with_params(query, id, 0).await;
+    }
+
+    /// Reads a `COM_STMT_PREPARE` and answers with a statement with `params` parameters and no columns.
+    async fn expect_prepare_with_params(&mut self, query: &str, id: u32, params: u16) {
+        self.seq_id = 0;
+        let packet = self.read().await;
+        assert_eq!(packet[0], Command::StmtPrepare as u8);
+        assert_eq!(std::str::from_utf8(&packet[1..]).unwrap(), query);
+        let mut ok = vec![0x00];
+        ok.extend_from_slice(&id.to_le_bytes());
+        ok.extend_from_slice(&[0, 0]);
+        ok.extend_from_slice(&params.to_le_bytes());
+        ok.extend_from_slice(&[0, 0, 0]);
+        self.write(&ok).await;
+        for _ in 0..params {
+            self.write_column_def("?").await;
+        }
+    }
+
+    /// Reads a `COM_STMT_EXECUTE` of the statement and returns the packet.
+    async fn read_execute(&mut self, id: u32) -> Vec<u8> {
+        self.seq_id = 0;
+        let packet = self.read().await;
+        assert_eq!(packet[0], Command::StmtExecute as u8);
+        assert_eq!(packet[1..5], id.to_le_bytes());
+        packet
+    }
+
+    async fn write_err(&mut self, code: u16, message: &str) {
+        let mut packet = vec![0xFF];
+        packet.extend_from_slice(&code.to_le_bytes());
+        packet.extend_from_slice(b"#HY000");
+        packet.extend_from_slice(message.as_bytes());
+        self.write(&packet).await;
+    }
+
+    /// Reads a command without response.
+    async fn expect_command(&mut self, command: Command, data: &[u8]) {
+        self.seq_id = 0;
+        let packet = self.read().await;
+        assert_eq!(packet[0], command as u8);
+        assert_eq!(&packet[1..], data);
+    }
+
+    /// Reads a `COM_QUERY` and checks the query.
+    async fn read_query(&mut self, query: &str) {
+        self.seq_id = 0;
+        let packet = self.read().await;
+        assert_eq!(packet[0], Command::Query as u8);
+        assert_eq!(std::str::from_utf8(&packet[1..]).unwrap(), query);
+    }
+
+    /// Reads a `COM_QUERY` and answers with an ok packet.
+    async fn expect_query(&mut self, query: &str, status: StatusFlags) {
+        self.read_query(query).await;
+        self.write_ok_with_status(status).await;
+    }
+}
+
+async fn connect_native(path: &std::path::Path) -> Connection {
+    Connection::connect(Arc::new(ConnectionOptions::<UnixStream> {
+        auth_plugin: Some(AuthPlugin::Native),
+        multi_statements: true,
+        local_infile: Some(Arc::new(CsvHandler)),
+        max_allowed_packet: Some(1024),
+        connection: UnixStreamOptions {
+            path: path.to_owned(),
+        },
+        ..Default::default()
+    }))
+    .await
+    .unwrap()
+}
+
+fn socket_path(name: &str) -> std::path::PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "mysql-connector-{}-{name}.sock",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+#[cfg(feature = "caching-sha2-password")]
+#[tokio::test]
+async fn unix_socket_sends_cleartext_password() {
+    let path = socket_path("sha2");
+    let listener = UnixListener::bind(&path).unwrap();
+
+    let server = tokio::spawn(async move {
+        let mut server = Server::accept(&listener).await;
+        server.write_handshake("caching_sha2_password").await;
+        server.read().await;
+        // request full authentication
+        server.write(&[0x01, 0x04]).await;
+        // the socket is secure, so the server key is not requested
+        assert_eq!(server.read().await, b"secret\0");
+        server.write_ok().await;
+        server.seq_id = 0;
+        assert_eq!(server.read().await, [0x01]);
+    });
+
+    let conn = Connection::connect(Arc::new(ConnectionOptions::<UnixStream> {
+        user: String::from("user"),
+        password: String::from("secret"),
+        max_allowed_packet: Some(1024),
+        connection: UnixStreamOptions { path: path.clone() },
+        ..Default::default()
+    }))
+    .await
+    .unwrap();
+    assert!(conn.data().is_secure());
+    conn.disconnect().await.unwrap();
+
+    server.await.unwrap();
+    let _ = std::fs::remove_file(&path);
+}
+
+#[tokio::test]
+async fn dropped_transaction_is_rolled_back() {
+    let path = socket_path("transaction");
+    let listener = UnixListener::bind(&path).unwrap();
+    let in_trans = StatusFlags::AUTOCOMMIT | StatusFlags::IN_TRANS;
+
+    let server = tokio::spawn(async move {
+        let mut server = Server::accept(&listener).await;
+        server.write_handshake("mysql_native_password").await;
+        server.read().await;
+        server.write_ok().await;
+
+        server
+            .expect_query(
+                "set transaction isolation level serializable",
+                StatusFlags::AUTOCOMMIT,
+            )
+            .await;
+        server
+            .expect_query(
+                "start transaction with consistent snapshot, read only",
+                in_trans,
+            )
+            .await;
+        server
+            .expect_query("savepoint mysql_connector_1", in_trans)
+            .await;
+        server
+            .expect_query("savepoint mysql_connector_2", in_trans)
+            .await;
+        // dropped savepoint
+        server
+            .expect_query("rollback to savepoint mysql_connector_2", in_trans)
+            .await;
+        server
+            .expect_query("release savepoint mysql_connector_1", in_trans)
+            .await;
+        // dropped transaction, rolled back before the next command
+        server
+            .expect_query("rollback", StatusFlags::AUTOCOMMIT)
+            .await;
+        server.expect_query("DO 1", StatusFlags::AUTOCOMMIT).await;
+    });
+
+    let mut conn = connect_native(&path).await;
+    {
+        let mut transaction = conn
+            .begin_with(TransactionOptions {
+                isolation_level: Some(IsolationLevel::Serializable),
+                access_mode: Some(AccessMode::ReadOnly),
+                consistent_snapshot: true,
+            })
+            .await
+            .unwrap();
+        assert!(transaction.data().in_transaction());
+        assert!(matches!(
+            transaction.begin().await,
+            Err(Error::Runtime(RuntimeError::TransactionActive))
+        ));
+        let mut savepoint = transaction.savepoint().await.unwrap();
+        drop(savepoint.savepoint().await.unwrap());
+        savepoint.commit().await.unwrap();
+    }
+    conn.execute_query("DO 1").await.unwrap();
+    assert!(!conn.data().in_transaction());
+
+    server.await.unwrap();
+    let _ = std::fs::remove_file(&path);
+}
+
+#[tokio::test]
+async fn multiple_result_sets() {
+    let path = socket_path("multi");
+    let listener = UnixListener::bind(&path).unwrap();
+    let more = StatusFlags::AUTOCOMMIT | StatusFlags::MORE_RESULTS_EXISTS;
+
+    let server = tokio::spawn(async move {
+        let mut server = Server::accept(&listener).await;
+        server.write_handshake("mysql_native_password").await;
+        let response = server.read().await;
+        let capabilities = CapabilityFlags::from_bits_truncate(u32::from_le_bytes(
+            response[..4].try_into().unwrap(),
+        ));
+        assert!(capabilities.contains(CapabilityFlags::MULTI_STATEMENTS));
+        server.write_ok().await;
+
+        for _ in 0..2 {
+            server.read_query("SELECT 'a'; SELECT 'b', 'c'; DO 1").await;
+            server.write_result_set("x", &["a"], more).await;
+            server.write_result_set("y", &["b", "c"], more).await;
+            server.write_ok().await;
+        }
+        server.read_query("SELECT 'd'").await;
+        server
+            .write_result_set("z", &["d"], StatusFlags::AUTOCOMMIT)
+            .await;
+        // the pending result sets are read before the next command is sent
+        server.expect_query("DO 2", StatusFlags::AUTOCOMMIT).await;
+    });
+
+    let mut conn = connect_native(&path).await;
+    let query = "SELECT 'a'; SELECT 'b', 'c'; DO 1";
+
+    let mut res = conn.query::<Vec<Value>>(query).await.unwrap();
+    assert_eq!(res.columns()[0].name(), "x");
+    assert_eq!(
+        res.collect().await.unwrap(),
+        [vec![Value::Bytes(b"a".to_vec())]]
+    );
+    assert!(res.has_next_result_set());
+    let mut res = res.next_result_set::<Vec<Value>>().await.unwrap().unwrap();
+    assert_eq!(res.columns()[0].name(), "y");
+    assert_eq!(
+        res.next().await.unwrap(),
+        Some(vec![Value::Bytes(b"b".to_vec())])
+    );
+    // skips the remaining row
+    let mut res = res.next_result_set::<Vec<Value>>().await.unwrap().unwrap();
+    assert!(res.columns().is_empty());
+    assert_eq!(res.next().await.unwrap(), None);
+    assert!(res.next_result_set::<Vec<Value>>().await.unwrap().is_none());
+
+    conn.query::<Vec<Value>>(query).await.unwrap();
+    assert!(matches!(
+        conn.execute_query("SELECT 'd'").await,
+        Err(Error::Runtime(RuntimeError::UnexpectedResultSet))
+    ));
+    conn.execute_query("DO 2").await.unwrap();
+
+    server.await.unwrap();
+    let _ = std::fs::remove_file(&path);
+}
+
+#[derive(Debug)]
+struct CsvHandler;
+
+impl LocalInfileHandler for CsvHandler {
+    fn open<'a>(
+        &'a self,
+        file_name: &'a str,
+    ) -> std::pin::Pin<
+        Box<
+            dyn std::future::Future<Output = std::io::Result<Option<LocalInfileReader>>>
+                + Send
+                + 'a,
+        >,
+    > {
+        Box::pin(async move {
+            Ok(match file_name {
+                "data.csv" => Some(Box::new(&b"1,a\n2,b\n"[..]) as LocalInfileReader),
+                _ => None,
+            })
+        })
+    }
+}
+
+#[tokio::test]
+async fn local_infile() {
+    let path = socket_path("infile");
+    let listener = UnixListener::bind(&path).unwrap();
+
+    let server = tokio::spawn(async move {
+        let mut server = Server::accept(&listener).await;
+        server.write_handshake("mysql_native_password").await;
+        let response = server.read().await;
+        let capabilities = CapabilityFlags::from_bits_truncate(u32::from_le_bytes(
+            response[..4].try_into().unwrap(),
+        ));
+        assert!(capabilities.contains(CapabilityFlags::LOCAL_FILES));
+        server.write_ok().await;
+
+        for (file, content) in [("data.csv", &b"1,a\n2,b\n"[..]), ("/etc/passwd", b"")] {
+            server
+                .read_query(&format!("LOAD DATA LOCAL INFILE '{file}' INTO TABLE t"))
+                .await;
+            server.write(&[b"\xFB", file.as_bytes()].concat()).await;
+            let mut received = Vec::new();
+            loop {
+                let packet = server.read().await;
+                if packet.is_empty() {
+                    break;
+                }
+                received.extend_from_slice(&packet);
+            }
+            assert_eq!(received, content);
+            server.write_ok().await;
+        }
+        server.expect_query("DO 1", StatusFlags::AUTOCOMMIT).await;
+    });
+
+    let mut conn = connect_native(&path).await;
+    conn.execute_query("LOAD DATA LOCAL INFILE 'data.csv' INTO TABLE t")
+        .await
+        .unwrap();
+    assert!(matches!(
+        conn.execute_query("LOAD DATA LOCAL INFILE '/etc/passwd' INTO TABLE t").await,
+        Err(Error::Runtime(RuntimeError::LocalInfileRejected(file))) if file == "/etc/passwd"
+    ));
+    // the connection is still usable
+    conn.execute_query("DO 1").await.unwrap();
+
+    server.await.unwrap();
+    let _ = std::fs::remove_file(&path);
+}
+
+#[tokio::test]
+async fn statement_cache() {
+    let path = socket_path("stmt-cache");
+    let listener = UnixListener::bind(&path).unwrap();
+
+    let server = tokio::spawn(async move {
+        let mut server = Server::accept(&listener).await;
+        server.write_handshake("mysql_native_password").await;
+        server.read().await;
+        server.write_ok().await;
+
+        server.expect_prepare("DO 1", 1).await;
+        // the second statement evicts the first one
+        server.expect_prepare("DO 2", 2).await;
+        server
+            .expect_command(Command::StmtClose, &1u32.to_le_bytes())
+            .await;
+        server.expect_command(Command::ResetConnection, &[]).await;
+        server.write_ok().await;
+        server.expect_prepare("DO 2", 3).await;
+    });
+
+    let mut conn = Connection::connect(Arc::new(ConnectionOptions::<UnixStream> {
+        auth_plugin: Some(AuthPlugin::Native),
+        max_allowed_packet: Some(1024),
+        stmt_cache_size: 1,
+        connection: UnixStreamOptions { path: path.clone() },
+        ..Default::default()
+    }))
+    .await
+    .unwrap();
+    conn.prepare_statement("DO 1").await.unwrap();
+    conn.prepare_statement("DO 1").await.unwrap();
+    conn.prepare_statement("DO 2").await.unwrap();
+    conn.prepare_statement("DO 2").await.unwrap();
+    // the server closes all statements on reset
+    conn.reset_connection().await.unwrap();
+    conn.prepare_statement("DO 2").await.unwrap();
+
+    server.await.unwrap();
+    let _ = std::fs::remove_file(&path);
+}
+
+#[tokio::test]
+async fn close_and_reset_statements() {
+    let path = socket_path("stmt-close");
+    let listener = UnixListener::bind(&path).unwrap();
+
+    let server = tokio::spawn(async move {
+        let mut server = Server::accept(&listener).await;
+        server.write_handshake("mysql_native_password").await;
+        server.read().await;
+        server.write_ok().await;
+
+        server.expect_prepare("DO 1", 1).await;
+        server
+            .expect_command(Command::StmtReset, &1u32.to_le_bytes())
+            .await;
+        server.write_ok().await;
+        server
+            .expect_command(Command::StmtClose, &1u32.to_le_bytes())
+            .await;
+        // closed statements are removed from the cache
+        server.expect_prepare("DO 1", 2).await;
+        server.expect_query("DO 2", StatusFlags::AUTOCOMMIT).await;
+    });
+
+    let mut conn = connect_native(&path).await;
+    let mut stmt = conn.prepare_statement("DO 1").await.unwrap();
+    stmt.reset().await.unwrap();
+    stmt.close().await.unwrap();
+    // cached, so it isn't closed on drop
+    drop(conn.prepare_statement("DO 1").await.unwrap());
+    conn.execute_query("DO 2").await.unwrap();
+
+    server.await.unwrap();
+    let _ = std::fs::remove_file(&path);
+}
+
+#[tokio::test]
+async fn datetimes_are_converted_from_and_to_the_session_time_zone() {
+    let path = socket_path("time-zone");
+    let listener = UnixListener::bind(&path).unwrap();
+
+    let server = tokio::spawn(async move {
+        let mut server = Server::accept(&listener).await;
+        server.write_handshake("mysql_native_password").await;
+        server.read().await;
+        server.write_ok().await;
+
+        server
+            .expect_query("set time_zone = '-02:30'", StatusFlags::AUTOCOMMIT)
+            .await;
+        server.read_query("select now()").await;
+        server.write(&[1]).await;
+        server.write_datetime_column_def("now()").await;
+        server.write(b"\x132024-01-01 00:15:00").await;
+        server.write_eof(StatusFlags::AUTOCOMMIT).await;
+
+        server.expect_prepare_with_params("DO ?", 1, 1).await;
+        let packet = server.read_execute(1).await;
+        // 2024-01-01 00:15:00 in the session time zone
+        assert!(packet.ends_with(&[7, 0xE8, 0x07, 1, 1, 0, 15, 0]));
+        server.write_ok().await;
+
+        server.expect_command(Command::ResetConnection, &[]).await;
+        server.write_ok().await;
+        server
+            .expect_query("set time_zone = '-02:30'", StatusFlags::AUTOCOMMIT)
+            .await;
+    });
+
+    let options = |seconds: i32| {
+        Arc::new(ConnectionOptions::<UnixStream> {
+            auth_plugin: Some(AuthPlugin::Native),
+            max_allowed_packet: Some(1024),
+            time_zone: Some(FixedOffset::east_opt(seconds).unwrap()),
+            connection: UnixStreamOptions { path: path.clone() },
+            ..Default::default()
+        })
+    };
+    // rejected before connecting
+    assert!(matches!(
+        Connection::connect(options(14 * 3600 + 30 * 60)).await,
+        Err(Error::Runtime(RuntimeError::UnsupportedTimeZone(_)))
+    ));
+    let mut conn = Connection::connect(options(-(2 * 3600 + 30 * 60)))
+        .await
+        .unwrap();
+
+    let now = Utc.with_ymd_and_hms(2024, 1, 1, 2, 45, 0).unwrap();
+    let rows = conn
+        .query::<DateTime<Utc>>("select now()")
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+    assert_eq!(rows, [now]);
+    conn.prepare_statement("DO ?")
+        .await
+        .unwrap()
+        .execute(&[Value::try_from(now).unwrap()])
+        .await
+        .unwrap();
+    conn.reset_connection().await.unwrap();
+
+    server.await.unwrap();
+    let _ = std::fs::remove_file(&path);
+}
+
+#[tokio::test]
+async fn dropped_statement_is_closed() {
+    let path = socket_path("stmt-drop");
+    let listener = UnixListener::bind(&path).unwrap();
+
+    let server = tokio::spawn(async move {
+        let mut server = Server::accept(&listener).await;
+        server.write_handshake("mysql_native_password").await;
+        server.read().await;
+        server.write_ok().await;
+
+        server.expect_prepare("DO 1", 1).await;
+        server
+            .expect_command(Command::StmtClose, &1u32.to_le_bytes())
+            .await;
+        server.expect_query("DO 2", StatusFlags::AUTOCOMMIT).await;
+    });
+
+    let mut conn = Connection::connect(Arc::new(ConnectionOptions::<UnixStream> {
+        auth_plugin: Some(AuthPlugin::Native),
+        max_allowed_packet: Some(1024),
+        stmt_cache_size: 0,
+        connection: UnixStreamOptions { path: path.clone() },
+        ..Default::default()
+    }))
+    .await
+    .unwrap();
+    drop(conn.prepare_statement("DO 1").await.unwrap());
+    conn.execute_query("DO 2").await.unwrap();
+
+    server.await.unwrap();
+    let _ = std::fs::remove_file(&path);
+}
+
+#[tokio::test]
+async fn statement_metadata() {
+    let path = socket_path("stmt-metadata");
+    let listener = UnixListener::bind(&path).unwrap();
+
+    let server = tokio::spawn(async move {
+        let mut server = Server::accept(&listener).await;
+        server.write_handshake("mysql_native_password").await;
+        server.read().await;
+        server.write_ok().await;
+
+        server.seq_id = 0;
+        let packet = server.read().await;
+        assert_eq!(packet[0], Command::StmtPrepare as u8);
+        // id 1, one column, one parameter, metadata follows
+        server
+            .write(&[0x00, 1, 0, 0, 0, 1, 0, 1, 0, 0, 0, 0, 1])
+            .await;
+        server.write_column_def("?").await;
+        server.write_column_def("x").await;
+
+        server.seq_id = 0;
+        let packet = server.read().await;
+        assert_eq!(packet[0], Command::StmtExecute as u8);
+        // one column without metadata
+        server.write(&[1, 0]).await;
+        server.write(&[0x00, 0x00, 1, b'a']).await;
+        server.write_eof(StatusFlags::AUTOCOMMIT).await;
+    });
+
+    let mut conn = Connection::connect(Arc::new(ConnectionOptions::<UnixStream> {
+        auth_plugin: Some(AuthPlugin::Native),
+        max_allowed_packet: Some(1024),
+        optional_resultset_metadata: true,
+        connection: UnixStreamOptions { path: path.clone() },
+        ..Default::default()
+    }))
+    .await
+    .unwrap();
+    let mut stmt = conn.prepare_statement("SELECT ? AS x").await.unwrap();
+    assert_eq!(stmt.params().len(), 1);
+    assert_eq!(stmt.columns()[0].name(), "x");
+    let mut res = stmt
+        .query::<_, Vec<Value>>(&[Value::Bytes(b"a".to_vec())])
+        .await
+        .unwrap();
+    assert_eq!(res.columns()[0].name(), "x");
+    assert_eq!(
+        res.collect().await.unwrap(),
+        [vec![Value::Bytes(b"a".to_vec())]]
+    );
+
+    server.await.unwrap();
+    let _ = std::fs::remove_file(&path);
+}
+
+#[tokio::test]
+async fn cursor_fetches_in_batches() {
+    let path = socket_path("cursor");
+    let listener = UnixListener::bind(&path).unwrap();
+    let cursor = StatusFlags::AUTOCOMMIT | StatusFlags::CURSOR_EXISTS;
+
+    let server = tokio::spawn(async move {
+        let mut server = Server::accept(&listener).await;
+        server.write_handshake("mysql_native_password").await;
+        server.read().await;
+        server.write_ok().await;
+
+        server.seq_id = 0;
+        server.read().await;
+        server.write(&[0x00, 1, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0]).await;
+        server.write_column_def("x").await;
+
+        server.seq_id = 0;
+        let packet = server.read().await;
+        assert_eq!(packet[0], Command::StmtExecute as u8);
+        assert_eq!(packet[5], CursorTypeFlags::READ_ONLY.bits());
+        server.write(&[1]).await;
+        server.write_column_def("x").await;
+        server.write_eof(cursor).await;
+
+        for rows in [&["a", "b"][..], &["c"]] {
+            server
+                .expect_command(Command::StmtFetch, &[1, 0, 0, 0, 2, 0, 0, 0])
+                .await;
+            for row in rows {
+                server.write(&[0x00, 0x00, 1, row.as_bytes()[0]]).await;
+            }
+            if rows.len() == 2 {
+                server.write_eof(cursor).await;
+            } else {
+                server.write_eof(cursor | StatusFlags::LAST_ROW_SENT).await;
+            }
+        }
+    });
+
+    let mut conn = connect_native(&path).await;
+    let mut stmt = conn.prepare_statement("SELECT x FROM t").await.unwrap();
+    let mut cursor = stmt
+        .query_cursor::<Value, Vec<Value>>(&[], 2)
+        .await
+        .unwrap();
+    assert_eq!(cursor.columns()[0].name(), "x");
+    assert_eq!(
+        cursor.collect().await.unwrap(),
+        [b"a", b"b", b"c"].map(|x| vec![Value::Bytes(x.to_vec())])
+    );
+    assert!(cursor.next().await.unwrap().is_none());
+
+    server.await.unwrap();
+    let _ = std::fs::remove_file(&path);
+}
+
+#[tokio::test]
+async fn execute_batch_pipelines_statements() {
+    let path = socket_path("batch");
+    let listener = UnixListener::bind(&path).unwrap();
+
+    let server = tokio::spawn(async move {
+        let mut server = Server::accept(&listener).await;
+        server.write_handshake("mysql_native_password").await;
+        server.read().await;
+        server.write_ok().await;
+
+        server.seq_id = 0;
+        server.read().await;
+        server.write(&[0x00, 1, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0]).await;
+        server.write_column_def("?").await;
+
+        // all requests are written before the first response
+        for fail in [None, Some(1)] {
+            for i in 1..=3i32 {
+                server.seq_id = 0;
+                let packet = server.read().await;
+                assert_eq!(packet[0], Command::StmtExecute as u8);
+                assert_eq!(packet[packet.len() - 4..], i.to_le_bytes());
+            }
+            for i in 0..3 {
+                server.seq_id = 1;
+                if fail == Some(i) {
+                    server.write_err(1062, "Duplicate entry").await;
+                } else {
+                    server
+                        .write_ok_with_ids(1, 5 + i as u8, StatusFlags::AUTOCOMMIT)
+                        .await;
+                }
+            }
+        }
+        server.expect_query("DO 1", StatusFlags::AUTOCOMMIT).await;
+    });
+
+    let mut conn = connect_native(&path).await;
+    let mut stmt = conn
+        .prepare_statement("INSERT INTO t (x) VALUES (?)")
+        .await
+        .unwrap();
+    let rows = [[Value::Int(1)], [Value::Int(2)], [Value::Int(3)]];
+    let result = stmt
+        .execute_batch(rows.iter().map(|x| &x[..]))
+        .await
+        .unwrap();
+    assert_eq!(result.affected_rows(), 3);
+    assert_eq!(result.last_insert_ids(), [5, 6, 7]);
+    assert!(matches!(
+        stmt.execute_batch(rows.iter().map(|x| &x[..])).await,
+        Err(Error::Server(_))
+    ));
+    assert!(matches!(
+        stmt.execute_batch([&[][..] as &[Value]]).await,
+        Err(Error::Runtime(RuntimeError::ParameterCountMismatch))
+    ));
+    drop(stmt);
+    conn.execute_query("DO 1").await.unwrap();
+
+    server.await.unwrap();
+    let _ = std::fs::remove_file(&path);
+}
+
+#[tokio::test]
+async fn execute_batch_uses_mariadb_bulk() {
+    let path = socket_path("bulk");
+    let listener = UnixListener::bind(&path).unwrap();
+
+    let server = tokio::spawn(async move {
+        let mut server = Server::accept(&listener).await;
+        server
+            .write_handshake_as(
+                "11.4.2-MariaDB",
+                MariadbCapabilityFlags::STMT_BULK_OPERATIONS | MariadbCapabilityFlags::PROGRESS,
+                "mysql_native_password",
+            )
+            .await;
+        let response = server.read().await;
+        assert_eq!(
+            response[28..32],
+            MariadbCapabilityFlags::STMT_BULK_OPERATIONS
+                .bits()
+                .to_le_bytes()
+        );
+        server.write_ok().await;
+
+        server.seq_id = 0;
+        server.read().await;
+        server.write(&[0x00, 1, 0, 0, 0, 0, 0, 2, 0, 0, 0, 0]).await;
+        server.write_column_def("?").await;
+        server.write_column_def("?").await;
+
+        server.seq_id = 0;
+        let packet = server.read().await;
+        #[rustfmt::skip]
+        assert_eq!(
+            packet,
+            [
+                0xFA, 1, 0, 0, 0, 128, 0,
+                ColumnType::Long as u8, 0, ColumnType::VarString as u8, 0,
+                0, 1, 0, 0, 0, 0, 1, b'a',
+                0, 2, 0, 0, 0, 1,
+            ]
+        );
+        server
+            .write_ok_with_ids(2, 10, StatusFlags::AUTOCOMMIT)
+            .await;
+    });
+
+    let mut conn = connect_native(&path).await;
+    assert!(conn.data().is_mariadb());
+    let mut stmt = conn
+        .prepare_statement("INSERT INTO t (x, y) VALUES (?, ?)")
+        .await
+        .unwrap();
+    let rows = [
+        [Value::Int(1), Value::Bytes(b"a".to_vec())],
+        [Value::Int(2), Value::Null],
+    ];
+    let result = stmt
+        .execute_batch(rows.iter().map(|x| &x[..]))
+        .await
+        .unwrap();
+    assert_eq!(result.affected_rows(), 2);
+    assert_eq!(result.last_insert_ids(), [10, 11]);
+
+    server.await.unwrap();
+    let _ = std::fs::remove_file(&path);
+}
+
+#[cfg(feature = "tls")]
+mod tls {
+    use {
+        super::{socket_path, Server},
+        crate::{
+            bitflags::CapabilityFlags, error::RuntimeError, types::AuthPlugin, Connection,
+            ConnectionOptions, Error, TlsOptions, TlsVerifyMode, UnixStreamOptions,
+        },
+        rcgen::{BasicConstraints, CertificateParams, IsCa, KeyPair},
+        std::sync::Arc,
+        tokio::net::{UnixListener, UnixStream},
+        tokio_rustls::{
+            rustls::{
+                crypto::ring,
+                pki_types::{CertificateDer, PrivateKeyDer},
+                RootCertStore, ServerConfig,
+            },
+            TlsAcceptor,
+        },
+    };
+
+    /// Certificate authority and a server certificate for `localhost` signed by it.
+    fn certificates() -> (CertificateDer<'static>, ServerConfig) {
+        let ca_key = KeyPair::generate().unwrap();
+        let mut ca_params = CertificateParams::new(Vec::new()).unwrap();
+        ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        let ca = ca_params.self_signed(&ca_key).unwrap();
+
+        let key = KeyPair::generate().unwrap();
+        let cert = CertificateParams::new(vec![String::from("localhost")])
+            .unwrap()
+            .signed_by(&key, &ca, &ca_key)
+            .unwrap();
+        let config = ServerConfig::builder_with_provider(Arc::new(ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .unwrap()
+            .with_no_client_auth()
+            .with_single_cert(
+                vec![cert.der().clone()],
+                PrivateKeyDer::Pkcs8(key.serialize_der().into()),
+            )
+            .unwrap();
+        (ca.der().clone(), config)
+    }
+
+    /// Connects with TLS and returns whether the handshake succeeded on both sides.
+    async fn connect(name: &str, trusted: bool, server_name: &str, mode: TlsVerifyMode) -> bool {
+        let path = socket_path(name);
+        let listener = UnixListener::bind(&path).unwrap();
+        let (ca, config) = certificates();
+
+        let server = tokio::spawn(async move {
+            let mut server = Server::accept(&listener).await;
+            server.ssl = true;
+            server.write_handshake("mysql_native_password").await;
+            let ssl_request = server.read().await;
+            assert_eq!(ssl_request.len(), 32);
+            let capabilities = CapabilityFlags::from_bits_truncate(u32::from_le_bytes(
+                ssl_request[..4].try_into().unwrap(),
+            ));
+            assert!(capabilities.contains(CapabilityFlags::SSL));
+
+            let acceptor = TlsAcceptor::from(Arc::new(config));
+            let Ok(stream) = acceptor.accept(server.stream).await else {
+                return false;
+            };
+            server.stream = Box::new(stream);
+            let response = server.read().await;
+            assert_eq!(&response[..4], &ssl_request[..4]);
+            server.write_ok().await;
+            true
+        });
+
+        let mut root_certs = RootCertStore::empty();
+        if trusted {
+            root_certs.add(ca).unwrap();
+        }
+        let conn = Connection::connect(Arc::new(ConnectionOptions::<UnixStream> {
+            auth_plugin: Some(AuthPlugin::Native),
+            max_allowed_packet: Some(1024),
+            tls: Some(TlsOptions {
+                root_certs,
+                verify_mode: mode,
+                server_name: Some(String::from(server_name)),
+                ..Default::default()
+            }),
+            connection: UnixStreamOptions { path: path.clone() },
+            ..Default::default()
+        }))
+        .await;
+        if let Ok(conn) = &conn {
+            assert!(conn.data().is_secure());
+        }
+
+        let accepted = server.await.unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(conn.is_ok(), accepted);
+        accepted
+    }
+
+    #[tokio::test]
+    async fn verify_full() {
+        assert!(connect("tls-full", true, "localhost", TlsVerifyMode::Full).await);
+        assert!(!connect("tls-full-name", true, "example.com", TlsVerifyMode::Full).await);
+        assert!(!connect("tls-full-ca", false, "localhost", TlsVerifyMode::Full).await);
+    }
+
+    #[tokio::test]
+    async fn verify_ca() {
+        assert!(connect("tls-ca", true, "example.com", TlsVerifyMode::VerifyCa).await);
+        assert!(
+            !connect(
+                "tls-ca-untrusted",
+                false,
+                "localhost",
+                TlsVerifyMode::VerifyCa
+            )
+            .await
+        );
+    }
+
+    #[tokio::test]
+    async fn verify_none() {
+        assert!(connect("tls-none", false, "example.com", TlsVerifyMode::None).await);
+    }
+
+    #[tokio::test]
+    async fn unsupported_by_server() {
+        let path = socket_path("tls-unsupported");
+        let listener = UnixListener::bind(&path).unwrap();
+
+        let server = tokio::spawn(async move {
+            let mut server = Server::accept(&listener).await;
+            server.write_handshake("mysql_native_password").await;
+        });
+
+        let conn = Connection::connect(Arc::new(ConnectionOptions::<UnixStream> {
+            auth_plugin: Some(AuthPlugin::Native),
+            tls: Some(TlsOptions::default()),
+            connection: UnixStreamOptions { path: path.clone() },
+            ..Default::default()
+        }))
+        .await;
+        assert!(matches!(
+            conn,
+            Err(Error::Runtime(RuntimeError::TlsUnsupported))
+        ));
+
+        server.await.unwrap();
+        let _ = std::fs::remove_file(&path);
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Debug, ModelData, FromQueryResult, ActiveModel, IntoQuery, Model)]
+#[mysql_connector(table = "map", primary = "id", auto_increment = "true")]
+struct Map {
+    id: u64,
+    name: String,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, ModelData, FromQueryResult, ActiveModel, IntoQuery, Model)]
+#[mysql_connector(table = "entity", primary = "id", auto_increment = "true")]
+struct Entity {
+    id: u64,
+    name: String,
+    map: Map,
+}
+
+#[tokio::test]
+async fn insert_many_in_chunks() {
+    let path = socket_path("insert-many");
+    let listener = UnixListener::bind(&path).unwrap();
+    let name = "x".repeat(400);
+
+    let server = tokio::spawn(async move {
+        let mut server = Server::accept(&listener).await;
+        server.write_handshake("mysql_native_password").await;
+        server.read().await;
+        server.write_ok().await;
+
+        // referenced models are inserted one by one before the entities
+        server
+            .expect_prepare_with_params("insert into `map` (`name`) values (?)", 1, 1)
+            .await;
+        for i in 0..3 {
+            server.read_execute(1).await;
+            server
+                .write_ok_with_ids(1, 10 + i, StatusFlags::AUTOCOMMIT)
+                .await;
+        }
+
+        // three rows don't fit into 1024 bytes
+        server
+            .expect_prepare_with_params(
+                "insert into `entity` (`name`, `map`) values (?, ?), (?, ?)",
+                2,
+                4,
+            )
+            .await;
+        let packet = server.read_execute(2).await;
+        assert!(packet.ends_with(&11u64.to_le_bytes()));
+        server
+            .write_ok_with_ids(2, 20, StatusFlags::AUTOCOMMIT)
+            .await;
+        // not cached, so closed after executing it
+        server
+            .expect_command(Command::StmtClose, &2u32.to_le_bytes())
+            .await;
+        server
+            .expect_prepare_with_params("insert into `entity` (`name`, `map`) values (?, ?)", 3, 2)
+            .await;
+        let packet = server.read_execute(3).await;
+        assert!(packet.ends_with(&12u64.to_le_bytes()));
+        server
+            .write_ok_with_ids(1, 22, StatusFlags::AUTOCOMMIT)
+            .await;
+        server
+            .expect_command(Command::StmtClose, &3u32.to_le_bytes())
+            .await;
+        server.expect_query("DO 1", StatusFlags::AUTOCOMMIT).await;
+    });
+
+    let mut conn = connect_native(&path).await;
+    let entities = (0..3).map(|i| {
+        Entity {
+            id: 0,
+            name: name.clone(),
+            map: Map {
+                id: 0,
+                name: format!("map {i}"),
+            },
+        }
+        .into_active_model()
+    });
+    let ids = ActiveModel::insert_many(entities, &mut conn).await.unwrap();
+    assert_eq!(ids, [20..22, 22..23]);
+    conn.execute_query("DO 1").await.unwrap();
+
+    server.await.unwrap();
+    let _ = std::fs::remove_file(&path);
+}
+
+#[tokio::test]
+async fn upsert_syntax_depends_on_server() {
+    let path = socket_path("upsert");
+    let listener = UnixListener::bind(&path).unwrap();
+    let versions = [
+        (
+            "8.0.19",
+            "as `new` on duplicate key update `name` = `new`.`name`",
+        ),
+        ("8.0.18", "on duplicate key update `name` = values(`name`)"),
+        (
+            "11.4.2-MariaDB",
+            "on duplicate key update `name` = values(`name`)",
+        ),
+    ];
+
+    let server = tokio::spawn(async move {
+        for (version, update) in versions {
+            let mut server = Server::accept(&listener).await;
+            server
+                .write_handshake_as(
+                    version,
+                    MariadbCapabilityFlags::empty(),
+                    "mysql_native_password",
+                )
+                .await;
+            server.read().await;
+            server.write_ok().await;
+
+            // the primary key of the existing row isn't updated
+            let query = format!("insert into `map` (`id`, `name`) values (?, ?) {update}");
+            server.expect_prepare_with_params(&query, 1, 2).await;
+            server.read_execute(1).await;
+            server
+                .write_ok_with_ids(1, 5, StatusFlags::AUTOCOMMIT)
+                .await;
+        }
+    });
+
+    for (version, _) in versions {
+        let mut conn = connect_native(&path).await;
+        assert_eq!(conn.data().is_mariadb(), version.ends_with("MariaDB"));
+        let mut map = Map {
+            id: 0,
+            name: String::from("map"),
+        }
+        .into_active_model();
+        map.id = ActiveValue::Set(5);
+        assert_eq!(map.upsert(&mut conn).await.unwrap(), 5);
+    }
+
+    server.await.unwrap();
+    let _ = std::fs::remove_file(&path);
+}
+
+#[allow(dead_code)]
+#[derive(Debug, ModelData, FromQueryResult, ActiveModel, IntoQuery, Model)]
+#[mysql_connector(table = "score", primary = "id", auto_increment = "true")]
+struct Score {
+    id: u64,
+    points: u64,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, ModelData, FromQueryResult, ActiveModel, IntoQuery, Model)]
+#[mysql_connector(table = "player", primary = "id", auto_increment = "true")]
+struct Player {
+    id: u64,
+    #[mysql_connector(has_many = "player")]
+    scores: Vec<Score>,
+}
+
+#[tokio::test]
+async fn has_many_loads_children_in_chunks() {
+    let path = socket_path("has-many");
+    let listener = UnixListener::bind(&path).unwrap();
+    let query = |keys: usize| {
+        format!(
+            "select `score`.`player`, `score`.`id`, `score`.`points` from `score` where `score`.`player` in (?{})",
+            ", ?".repeat(keys - 1)
+        )
+    };
+
+    let server = tokio::spawn(async move {
+        let mut server = Server::accept(&listener).await;
+        server.write_handshake("mysql_native_password").await;
+        server.read().await;
+        server.write_ok().await;
+
+        // the keys of 100 players fill an execute packet of 1024 bytes
+        for (id, keys, rows) in [
+            (1, 100, &[[2u64, 1, 10], [2, 2, 20]][..]),
+            (2, 50, &[[150, 3, 30]]),
+        ] {
+            server
+                .expect_prepare_with_params(&query(keys), id, keys as u16)
+                .await;
+            let packet = server.read_execute(id).await;
+            let first_key = (id as u64 - 1) * 100 + 1;
+            assert!(packet.ends_with(&(first_key + keys as u64 - 1).to_le_bytes()));
+            server.write(&[3]).await;
+            for column in ["player", "id", "points"] {
+                server.write_bigint_column_def("score", column).await;
+            }
+            for row in rows {
+                let mut packet = vec![0x00, 0x00];
+                for value in row {
+                    packet.extend_from_slice(&value.to_le_bytes());
+                }
+                server.write(&packet).await;
+            }
+            server.write_eof(StatusFlags::AUTOCOMMIT).await;
+        }
+    });
+
+    let mut conn = connect_native(&path).await;
+    let mut players = (1..=150)
+        .map(|id| Player {
+            id,
+            scores: Vec::new(),
+        })
+        .collect::<Vec<_>>();
+    Player::scores()
+        .load(&mut players, &mut conn)
+        .await
+        .unwrap();
+    let scores = |player: &Player| {
+        player
+            .scores
+            .iter()
+            .map(|x| (x.id, x.points))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(scores(&players[1]), [(1, 10), (2, 20)]);
+    assert_eq!(scores(&players[149]), [(3, 30)]);
+    assert!(players
+        .iter()
+        .enumerate()
+        .all(|(i, x)| i == 1 || i == 149 || x.scores.is_empty()));
+
+    server.await.unwrap();
+    let _ = std::fs::remove_file(&path);
+}
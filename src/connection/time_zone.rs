@@ -0,0 +1,76 @@
+use {
+    super::Connection,
+    crate::{
+        error::RuntimeError,
+        types::{SimpleValue, Value},
+    },
+    chrono::{Duration, FixedOffset, NaiveDateTime},
+};
+
+/// Checks that the offset is a valid session `time_zone`, i.e. whole minutes from `-13:59` to `+14:00`.
+pub(super) fn check_time_zone(offset: FixedOffset) -> Result<(), RuntimeError> {
+    let seconds = offset.local_minus_utc();
+    match seconds % 60 == 0 && (-(13 * 3600 + 59 * 60)..=14 * 3600).contains(&seconds) {
+        true => Ok(()),
+        false => Err(RuntimeError::UnsupportedTimeZone(offset)),
+    }
+}
+
+/// Adds `seconds` to a datetime. Zero dates and results out of range are kept as they are.
+fn shift(value: &mut Value, seconds: i32) {
+    if let Value::Datetime(..) = value {
+        let shifted = TryInto::<NaiveDateTime>::try_into(value.clone())
+            .ok()
+            .and_then(|x| x.checked_add_signed(Duration::seconds(seconds.into())))
+            .and_then(|x| Value::try_from(x).ok());
+        if let Some(shifted) = shifted {
+            *value = shifted;
+        }
+    }
+}
+
+impl Connection {
+    /// Seconds to add to UTC to get the session `time_zone`, `None` if the values don't have to be converted.
+    fn time_zone_offset(&self) -> Option<i32> {
+        self.options
+            .time_zone()
+            .map(|x| x.local_minus_utc())
+            .filter(|x| *x != 0)
+    }
+
+    /// Converts the `DATETIME` and `TIMESTAMP` values of a row from the session `time_zone` to UTC.
+    pub(super) fn row_to_utc(&self, row: &mut [Value]) {
+        if let Some(offset) = self.time_zone_offset() {
+            for value in row {
+                shift(value, -offset);
+            }
+        }
+    }
+
+    /// Converts the datetime parameters from UTC to the session `time_zone`.
+    ///
+    /// Returns `None` if no parameter has to be converted, so the parameters can be sent as they are.
+    pub(super) fn params_from_utc<V: SimpleValue>(&self, params: &[V]) -> Option<Vec<Value>> {
+        self.rows_from_utc(&[params])
+            .and_then(|x| x.into_iter().next())
+    }
+
+    /// Converts the datetime parameters of all rows, see [`params_from_utc`](Self::params_from_utc).
+    pub(super) fn rows_from_utc<V: SimpleValue>(&self, rows: &[&[V]]) -> Option<Vec<Vec<Value>>> {
+        let offset = self.time_zone_offset()?;
+        let is_datetime = |x: &V| matches!(x.value(), Value::Datetime(..));
+        if !rows.iter().any(|row| row.iter().any(is_datetime)) {
+            return None;
+        }
+        let rows = rows.iter().map(|row| {
+            row.iter()
+                .map(|x| {
+                    let mut value = x.value().clone();
+                    shift(&mut value, offset);
+                    value
+                })
+                .collect()
+        });
+        Some(rows.collect())
+    }
+}
@@ -0,0 +1,180 @@
+use {
+    super::Connection,
+    crate::{error::RuntimeError, Error, Timeout},
+    std::sync::Arc,
+    tokio_rustls::{
+        rustls::{
+            self,
+            client::{
+                danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier},
+                verify_server_cert_signed_by_trust_anchor, WebPkiServerVerifier,
+            },
+            crypto::{verify_tls12_signature, verify_tls13_signature, CryptoProvider},
+            pki_types::{CertificateDer, PrivateKeyDer, ServerName, UnixTime},
+            server::ParsedCertificate,
+            ClientConfig, DigitallySignedStruct, RootCertStore, SignatureScheme,
+        },
+        TlsConnector,
+    },
+};
+
+/// How the certificate of the server is verified.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TlsVerifyMode {
+    /// Verify the certificate chain and that the certificate is valid for the server name.
+    #[default]
+    Full,
+    /// Verify the certificate chain, but accept certificates issued for any name.
+    VerifyCa,
+    /// Accept any certificate.
+    ///
+    /// This only protects against passive eavesdropping, as it allows man-in-the-middle attacks.
+    None,
+}
+
+#[derive(Debug)]
+pub struct TlsOptions {
+    /// Certificate authorities used to verify the server certificate.
+    pub root_certs: RootCertStore,
+    /// Certificate chain and private key used if the server requires client certificates.
+    pub client_cert: Option<(Vec<CertificateDer<'static>>, PrivateKeyDer<'static>)>,
+    pub verify_mode: TlsVerifyMode,
+    /// Name used for SNI and certificate verification.
+    ///
+    /// Defaults to the host of the underlying [`Stream`](crate::Stream).
+    pub server_name: Option<String>,
+}
+
+impl Default for TlsOptions {
+    fn default() -> Self {
+        Self {
+            root_certs: RootCertStore::empty(),
+            client_cert: None,
+            verify_mode: TlsVerifyMode::default(),
+            server_name: None,
+        }
+    }
+}
+
+impl TlsOptions {
+    fn client_config(&self) -> Result<ClientConfig, rustls::Error> {
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+        let roots = Arc::new(self.root_certs.clone());
+        let builder = ClientConfig::builder_with_provider(provider.clone())
+            .with_safe_default_protocol_versions()?;
+        let builder = match self.verify_mode {
+            TlsVerifyMode::Full => builder.with_webpki_verifier(
+                WebPkiServerVerifier::builder_with_provider(roots, provider)
+                    .build()
+                    .map_err(|err| rustls::Error::General(err.to_string()))?,
+            ),
+            TlsVerifyMode::VerifyCa | TlsVerifyMode::None => builder
+                .dangerous()
+                .with_custom_certificate_verifier(Arc::new(Verifier {
+                    roots: match self.verify_mode {
+                        TlsVerifyMode::VerifyCa => Some(roots),
+                        _ => None,
+                    },
+                    provider,
+                })),
+        };
+        match &self.client_cert {
+            Some((certs, key)) => builder.with_client_auth_cert(certs.clone(), key.clone_key()),
+            None => Ok(builder.with_no_client_auth()),
+        }
+    }
+}
+
+/// Verifier for [`TlsVerifyMode::VerifyCa`] (with roots) and [`TlsVerifyMode::None`] (without roots).
+#[derive(Debug)]
+struct Verifier {
+    roots: Option<Arc<RootCertStore>>,
+    provider: Arc<CryptoProvider>,
+}
+
+impl ServerCertVerifier for Verifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        if let Some(roots) = &self.roots {
+            verify_server_cert_signed_by_trust_anchor(
+                &ParsedCertificate::try_from(end_entity)?,
+                roots,
+                intermediates,
+                now,
+                self.provider.signature_verification_algorithms.all,
+            )?;
+        }
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.provider
+            .signature_verification_algorithms
+            .supported_schemes()
+    }
+}
+
+impl Connection {
+    /// Replaces the underlying stream with a TLS stream.
+    ///
+    /// The SSL request has to be sent before calling this.
+    pub(super) async fn upgrade_to_tls(
+        &mut self,
+        options: &TlsOptions,
+        host: Option<&str>,
+    ) -> Result<(), Error> {
+        let server_name = options
+            .server_name
+            .as_deref()
+            .or(host)
+            .ok_or(RuntimeError::InvalidServerName(None))?;
+        let server_name = ServerName::try_from(server_name.to_owned())
+            .map_err(|_| RuntimeError::InvalidServerName(Some(server_name.to_owned())))?;
+        let config = options.client_config().map_err(Error::io_invalid_data)?;
+
+        let stream = std::mem::replace(&mut self.stream, Box::new(tokio::io::empty()));
+        let stream = Timeout::new(
+            TlsConnector::from(Arc::new(config)).connect(server_name, stream),
+            self.data.sleep,
+            self.options.timeout(),
+        )
+        .await??;
+        self.stream = Box::new(stream);
+        self.data.secure = true;
+        Ok(())
+    }
+}
@@ -0,0 +1,188 @@
+use {
+    super::{Command, Connection},
+    crate::{error::RuntimeError, Error},
+    std::ops::{Deref, DerefMut},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IsolationLevel {
+    ReadUncommitted,
+    ReadCommitted,
+    RepeatableRead,
+    Serializable,
+}
+
+impl IsolationLevel {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::ReadUncommitted => "read uncommitted",
+            Self::ReadCommitted => "read committed",
+            Self::RepeatableRead => "repeatable read",
+            Self::Serializable => "serializable",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessMode {
+    ReadWrite,
+    ReadOnly,
+}
+
+impl AccessMode {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::ReadWrite => "read write",
+            Self::ReadOnly => "read only",
+        }
+    }
+}
+
+/// Options for [`Connection::begin_with`].
+///
+/// Fields set to `None` use the session defaults.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TransactionOptions {
+    pub isolation_level: Option<IsolationLevel>,
+    pub access_mode: Option<AccessMode>,
+    /// Start a consistent read (`with consistent snapshot`).
+    pub consistent_snapshot: bool,
+}
+
+impl TransactionOptions {
+    fn start_query(&self) -> String {
+        let mut characteristics = Vec::new();
+        if self.consistent_snapshot {
+            characteristics.push("with consistent snapshot");
+        }
+        if let Some(access_mode) = self.access_mode {
+            characteristics.push(access_mode.as_str());
+        }
+        if characteristics.is_empty() {
+            String::from("start transaction")
+        } else {
+            format!("start transaction {}", characteristics.join(", "))
+        }
+    }
+}
+
+fn savepoint_name(depth: usize) -> String {
+    format!("mysql_connector_{depth}")
+}
+
+fn rollback_query(depth: usize) -> String {
+    match depth {
+        0 => String::from("rollback"),
+        depth => format!("rollback to savepoint {}", savepoint_name(depth)),
+    }
+}
+
+/// Guard for an active transaction, which derefs to the [`Connection`].
+///
+/// If the guard is dropped without calling [`commit`](Self::commit) or [`rollback`](Self::rollback),
+/// the transaction is rolled back. As this can't be done in `drop`, the rollback is sent before the next command.
+#[derive(Debug)]
+pub struct Transaction<'a> {
+    conn: &'a mut Connection,
+    /// `0` for the transaction itself, nesting level of the savepoint otherwise.
+    depth: usize,
+    finished: bool,
+}
+
+impl Transaction<'_> {
+    /// Creates a savepoint, which is released on commit and rolled back on rollback or drop.
+    pub async fn savepoint(&mut self) -> Result<Transaction<'_>, Error> {
+        self.conn.cleanup().await?;
+        if !self.conn.data.in_transaction() {
+            return Err(RuntimeError::NoTransaction.into());
+        }
+        let depth = self.depth + 1;
+        self.conn
+            .execute_query(&format!("savepoint {}", savepoint_name(depth)))
+            .await?;
+        Ok(Transaction {
+            conn: &mut *self.conn,
+            depth,
+            finished: false,
+        })
+    }
+
+    pub async fn commit(mut self) -> Result<(), Error> {
+        let query = match self.depth {
+            0 => String::from("commit"),
+            depth => format!("release savepoint {}", savepoint_name(depth)),
+        };
+        self.conn.execute_query(&query).await?;
+        self.finished = true;
+        Ok(())
+    }
+
+    pub async fn rollback(mut self) -> Result<(), Error> {
+        self.conn.execute_query(&rollback_query(self.depth)).await?;
+        self.finished = true;
+        Ok(())
+    }
+}
+
+impl Deref for Transaction<'_> {
+    type Target = Connection;
+
+    fn deref(&self) -> &Self::Target {
+        self.conn
+    }
+}
+
+impl DerefMut for Transaction<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.conn
+    }
+}
+
+impl Drop for Transaction<'_> {
+    fn drop(&mut self) {
+        // the server may have ended the transaction already, e.g. by an implicit commit
+        if !self.finished && self.conn.data.in_transaction() {
+            self.conn.pending_rollback = Some(match self.conn.pending_rollback {
+                Some(depth) => depth.min(self.depth),
+                None => self.depth,
+            });
+        }
+    }
+}
+
+impl Connection {
+    pub async fn begin(&mut self) -> Result<Transaction<'_>, Error> {
+        self.begin_with(TransactionOptions::default()).await
+    }
+
+    pub async fn begin_with(
+        &mut self,
+        options: TransactionOptions,
+    ) -> Result<Transaction<'_>, Error> {
+        self.cleanup().await?;
+        if self.data.in_transaction() {
+            return Err(RuntimeError::TransactionActive.into());
+        }
+        if let Some(isolation_level) = options.isolation_level {
+            self.execute_query(&format!(
+                "set transaction isolation level {}",
+                isolation_level.as_str()
+            ))
+            .await?;
+        }
+        self.execute_query(&options.start_query()).await?;
+        Ok(Transaction {
+            conn: self,
+            depth: 0,
+            finished: false,
+        })
+    }
+
+    /// Sends the rollback of a dropped [`Transaction`].
+    pub(super) async fn execute_pending_rollback(&mut self, depth: usize) -> Result<(), Error> {
+        self.send_command(Command::Query, rollback_query(depth))
+            .await?;
+        self.read_response().await??;
+        Ok(())
+    }
+}
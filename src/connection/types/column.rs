@@ -29,6 +29,19 @@ impl<'a> TryFrom<ColumnDef<'a>> for Column {
 }
 
 impl Column {
+    /// Column of the table (or alias) `table`, without type information.
+    #[cfg(test)]
+    pub(crate) fn with_table(table: &str, name: &str) -> Self {
+        Self {
+            org_name: name.to_owned(),
+            name: name.to_owned(),
+            org_table: table.to_owned(),
+            table: table.to_owned(),
+            r#type: ColumnType::Null,
+            flags: ColumnFlags::empty(),
+        }
+    }
+
     pub fn org_name(&self) -> &str {
         &self.org_name
     }
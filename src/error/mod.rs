@@ -1,5 +1,6 @@
 mod parse;
 mod protocol;
+mod url;
 
 use {
     crate::{connection::types::AuthPlugin, packets::ErrPacket},
@@ -9,6 +10,7 @@ use {
 pub use {
     parse::{InvalidFlags, ParseError},
     protocol::{ProtocolError, SerializeError},
+    url::UrlError,
 };
 
 #[derive(Debug)]
@@ -22,6 +24,30 @@ pub enum RuntimeError {
     ParameterCountMismatch,
     InsecureAuth,
     AuthPluginMismatch(AuthPluginMismatch),
+    /// TLS was requested, but the server doesn't support it.
+    TlsUnsupported,
+    /// The server name used for TLS is missing (`None`) or invalid.
+    InvalidServerName(Option<String>),
+    /// A transaction was started while another one is active.
+    TransactionActive,
+    /// The transaction was ended by the server, e.g. by an implicit commit.
+    NoTransaction,
+    /// The server requested a file, which wasn't provided by the [`LocalInfileHandler`](crate::LocalInfileHandler).
+    LocalInfileRejected(String),
+    /// Reading the file requested by the server failed.
+    LocalInfileIo(io::Error),
+    /// The server omitted the column definitions of a result set, whose columns are unknown.
+    MissingResultSetMetadata,
+    /// The statement passed to [`Connection::execute_query`](crate::Connection::execute_query) returned rows.
+    UnexpectedResultSet,
+    /// The connection url couldn't be parsed.
+    InvalidUrl(UrlError),
+    /// The query built by [`IntoQuery::build_query`](crate::model::IntoQuery::build_query)
+    /// doesn't start with `select `, so no column can be added.
+    InvalidModelQuery(String),
+    /// [`ConnectionOptions::time_zone`](crate::ConnectionOptions::time_zone) isn't whole minutes
+    /// from `-13:59` to `+14:00`.
+    UnsupportedTimeZone(chrono::FixedOffset),
 }
 
 impl RuntimeError {
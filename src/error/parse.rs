@@ -19,9 +19,11 @@ impl fmt::Debug for WrongValue {
 pub enum InvalidFlags {
     Status(u16),
     Capability(u32),
+    MariadbCapability(u32),
     CursorType(u8),
     StmtExecuteParams(u8),
     StmtExecuteParam(u8),
+    StmtBulk(u16),
     Column(u16),
     ColumnType(u8),
 }
@@ -36,6 +38,9 @@ pub enum ParseError {
     UnknownBitflags(InvalidFlags),
     Utf8(Utf8Error),
     FromUtf8(FromUtf8Error),
+    #[cfg(feature = "json")]
+    #[cfg_attr(doc, doc(cfg(feature = "json")))]
+    Json(serde_json::Error),
 }
 
 impl ParseError {
@@ -79,3 +84,11 @@ impl From<FromUtf8Error> for ProtocolError {
         ParseError::FromUtf8(value).into()
     }
 }
+
+#[cfg(feature = "json")]
+#[cfg_attr(doc, doc(cfg(feature = "json")))]
+impl From<serde_json::Error> for ParseError {
+    fn from(value: serde_json::Error) -> Self {
+        ParseError::Json(value)
+    }
+}
@@ -0,0 +1,49 @@
+use {
+    super::{Error, RuntimeError},
+    std::fmt,
+};
+
+/// Error returned when parsing a connection url fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UrlError {
+    /// The url doesn't start with `mysql://`.
+    InvalidScheme(String),
+    MissingHost,
+    InvalidPort(String),
+    /// A percent-encoded part is malformed or not valid UTF-8.
+    InvalidEncoding(String),
+    UnknownParameter(String),
+    /// Parameter name and value
+    InvalidParameter(String, String),
+}
+
+impl fmt::Display for UrlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidScheme(scheme) => {
+                write!(f, "invalid scheme `{scheme}`, expected `mysql://`")
+            }
+            Self::MissingHost => f.write_str("missing host"),
+            Self::InvalidPort(port) => write!(f, "invalid port `{port}`"),
+            Self::InvalidEncoding(value) => write!(f, "invalid percent-encoding in `{value}`"),
+            Self::UnknownParameter(name) => write!(f, "unknown parameter `{name}`"),
+            Self::InvalidParameter(name, value) => {
+                write!(f, "invalid value `{value}` for parameter `{name}`")
+            }
+        }
+    }
+}
+
+impl std::error::Error for UrlError {}
+
+impl From<UrlError> for RuntimeError {
+    fn from(value: UrlError) -> Self {
+        RuntimeError::InvalidUrl(value)
+    }
+}
+
+impl From<UrlError> for Error {
+    fn from(value: UrlError) -> Self {
+        RuntimeError::InvalidUrl(value).into()
+    }
+}
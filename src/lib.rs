@@ -18,3 +18,11 @@ pub use utils::crypt::PublicKey;
 #[cfg(feature = "tcpstream")]
 #[cfg_attr(doc, doc(cfg(feature = "tcpstream")))]
 pub use tokio::net::TcpStream;
+
+#[cfg(all(unix, feature = "unix-socket"))]
+#[cfg_attr(doc, doc(cfg(all(unix, feature = "unix-socket"))))]
+pub use tokio::net::UnixStream;
+
+#[cfg(feature = "tls")]
+#[cfg_attr(doc, doc(cfg(feature = "tls")))]
+pub use tokio_rustls::rustls;
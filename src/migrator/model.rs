@@ -60,6 +60,23 @@ pub(super) struct MigrationMapping {
 }
 
 impl FromQueryResultMapping<MigrationModel> for MigrationMapping {
+    fn all_null(&self, row: &[Value]) -> bool {
+        [self.version_0, self.version_1, self.version_2, self.name]
+            .into_iter()
+            .flatten()
+            .all(|i| matches!(row[i], Value::Null))
+    }
+
+    fn set_mapping(&mut self, column: &Column, table: &str, index: usize) {
+        self.set_mapping_inner(
+            column,
+            table
+                .strip_prefix(MigrationModel::TABLE_WITH_POINT)
+                .unwrap_or(table),
+            index,
+        )
+    }
+
     fn set_mapping_inner(&mut self, column: &Column, _name: &str, index: usize) {
         *match column.org_name() {
             "version_0" => &mut self.version_0,
@@ -3,10 +3,11 @@ use {
     crate::{model::Model, Connection, Error},
 };
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub enum ActiveReference<T: Model> {
     Set(T::Primary),
     Insert(T::ActiveModel),
+    #[default]
     Unset,
 }
 
@@ -21,7 +22,8 @@ impl<T: Model> ActiveReference<T> {
             Self::Set(id) => vec.push(NamedValue(name, id.into())),
             Self::Insert(model) => {
                 let primary = model.primary();
-                let last_insert_id = model.insert(conn).await?;
+                // boxed, as the future would be infinite for self-referential models
+                let last_insert_id = Box::pin(model.insert(conn)).await?;
                 vec.push(NamedValue(name, primary.unwrap_or(last_insert_id.into())));
             }
             Self::Unset => (),
@@ -29,9 +31,3 @@ impl<T: Model> ActiveReference<T> {
         Ok(())
     }
 }
-
-impl<T: Model> Default for ActiveReference<T> {
-    fn default() -> Self {
-        Self::Unset
-    }
-}
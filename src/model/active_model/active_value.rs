@@ -3,9 +3,10 @@ use {
     crate::{error::SerializeError, types::Value},
 };
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub enum ActiveValue<T> {
     Set(T),
+    #[default]
     Unset,
 }
 
@@ -26,9 +27,3 @@ where
         Ok(())
     }
 }
-
-impl<T> Default for ActiveValue<T> {
-    fn default() -> Self {
-        Self::Unset
-    }
-}
@@ -6,6 +6,8 @@ mod update_model;
 use {
     super::Model,
     crate::{error::Error, types::Value, Connection},
+    named_value::InsertMode,
+    std::ops::Range,
 };
 
 pub use {
@@ -15,6 +17,9 @@ pub use {
 
 #[allow(async_fn_in_trait)]
 pub trait ActiveModel<ModelData: super::ModelData>: Default {
+    /// Column of the primary key, which isn't changed by [`upsert`](Self::upsert).
+    const PRIMARY: Option<&'static str> = None;
+
     async fn into_values(self, conn: &mut Connection) -> Result<Vec<NamedValue>, Error>;
 
     fn primary(&self) -> Option<Value>;
@@ -23,11 +28,133 @@ pub trait ActiveModel<ModelData: super::ModelData>: Default {
     where
         Self: Sized,
     {
-        let values = self.into_values(conn).await?;
-        let stmt = NamedValue::into_insert(&values, ModelData::TABLE)?;
-        let mut stmt = conn.prepare_statement(&stmt).await?;
-        stmt.execute(&values).await.map(|x| x.last_insert_id())
+        insert_with::<ModelData, _>(self, InsertMode::Insert, conn).await
+    }
+
+    /// Inserts the model, or updates the set fields of the row with the same primary or unique key.
+    ///
+    /// The primary key of the existing row is kept.
+    async fn upsert(self, conn: &mut Connection) -> Result<u64, Error>
+    where
+        Self: Sized,
+    {
+        let data = conn.data();
+        let alias = !data.is_mariadb() && data.version() >= (8, 0, 19);
+        let mode = InsertMode::Upsert {
+            alias,
+            primary: Self::PRIMARY,
+        };
+        insert_with::<ModelData, _>(self, mode, conn).await
+    }
+
+    /// Inserts the model, unless a row with the same primary or unique key exists.
+    async fn insert_ignore(self, conn: &mut Connection) -> Result<u64, Error>
+    where
+        Self: Sized,
+    {
+        insert_with::<ModelData, _>(self, InsertMode::Ignore, conn).await
+    }
+
+    /// Inserts the model, deleting a row with the same primary or unique key first.
+    async fn replace(self, conn: &mut Connection) -> Result<u64, Error>
+    where
+        Self: Sized,
+    {
+        insert_with::<ModelData, _>(self, InsertMode::Replace, conn).await
     }
+
+    /// Inserts the models with as few multi-row insert statements as possible.
+    /// The statements bypass the statement cache and are closed after executing them.
+    ///
+    /// Referenced models which have to be inserted are inserted first.
+    /// Returns the range of generated ids for every statement, derived from the first generated id
+    /// and the number of inserted rows (assuming `auto_increment_increment = 1`).
+    /// The range is empty if no id was generated.
+    async fn insert_many<I>(models: I, conn: &mut Connection) -> Result<Vec<Range<u64>>, Error>
+    where
+        Self: Sized,
+        I: IntoIterator<Item = Self>,
+    {
+        let mut rows = Vec::new();
+        for model in models {
+            rows.push(model.into_values(conn).await?);
+        }
+
+        let mut ids = Vec::new();
+        for chunk in insert_chunks(&rows, conn.data().max_allowed_packet()) {
+            let rows = &mut rows[chunk];
+            let stmt = NamedValue::into_insert_many(
+                &rows[0],
+                rows.len(),
+                ModelData::TABLE,
+                InsertMode::Insert,
+            )?;
+            let values = rows.iter_mut().flat_map(std::mem::take).collect::<Vec<_>>();
+            // every chunk size is a different statement, so they aren't cached
+            let mut stmt = conn.prepare_statement_uncached(&stmt).await?;
+            let ok = stmt.execute(&values).await?;
+            stmt.close().await?;
+            let first_id = ok.last_insert_id();
+            ids.push(match first_id {
+                0 => 0..0,
+                _ => first_id..first_id + ok.affected_rows(),
+            });
+        }
+        Ok(ids)
+    }
+}
+
+async fn insert_with<M: super::ModelData, A: ActiveModel<M>>(
+    model: A,
+    mut mode: InsertMode,
+    conn: &mut Connection,
+) -> Result<u64, Error> {
+    let values = model.into_values(conn).await?;
+    if let InsertMode::Upsert { primary, .. } = mode {
+        if values.iter().all(|x| Some(x.name()) == primary) {
+            // there is nothing to update
+            mode = InsertMode::Ignore;
+        }
+    }
+    let stmt = NamedValue::into_insert(&values, M::TABLE, mode)?;
+    let mut stmt = conn.prepare_statement(&stmt).await?;
+    stmt.execute(&values).await.map(|x| x.last_insert_id())
+}
+
+/// Splits the rows into runs that can be inserted by one statement.
+///
+/// The rows of a run have the same columns, at most `u16::MAX` parameters
+/// and fit into an execute packet of `max_len` bytes.
+fn insert_chunks(rows: &[Vec<NamedValue>], max_len: usize) -> Vec<Range<usize>> {
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    let mut params = 0;
+    let mut len = 0;
+    for (i, row) in rows.iter().enumerate() {
+        let row_len = row
+            .iter()
+            .map(|x| 2 + x.value().bin_len() as usize)
+            .sum::<usize>();
+        let same_columns = rows[start]
+            .iter()
+            .map(NamedValue::name)
+            .eq(row.iter().map(NamedValue::name));
+        let packet_len = 11 + (params + row.len()).div_ceil(8) + len + row_len;
+        if i != start
+            && (!same_columns || params + row.len() > u16::MAX as usize || packet_len > max_len)
+        {
+            chunks.push(start..i);
+            start = i;
+            params = 0;
+            len = 0;
+        }
+        params += row.len();
+        len += row_len;
+    }
+    if start < rows.len() {
+        chunks.push(start..rows.len());
+    }
+    chunks
 }
 
 pub trait HasActiveModel: super::ModelData {
@@ -38,3 +165,83 @@ pub trait HasActiveModel: super::ModelData {
     /// If the model has a primary key that is auto increment, it has to be set to [`ActiveValue::Unset`]
     fn into_active_model(self) -> Self::ActiveModel;
 }
+
+#[cfg(test)]
+mod test {
+    use {
+        super::{insert_chunks, InsertMode, NamedValue},
+        crate::types::Value,
+    };
+
+    fn row(names: &[&'static str]) -> Vec<NamedValue> {
+        names.iter().map(|x| NamedValue(x, Value::Int(1))).collect()
+    }
+
+    #[test]
+    fn insert_statements() {
+        assert_eq!(
+            NamedValue::into_insert_many(&row(&["a", "b"]), 2, "t", InsertMode::Insert).unwrap(),
+            "insert into `t` (`a`, `b`) values (?, ?), (?, ?)"
+        );
+        assert_eq!(
+            NamedValue::into_insert(&row(&["a"]), "t", InsertMode::Ignore).unwrap(),
+            "insert ignore into `t` (`a`) values (?)"
+        );
+        assert_eq!(
+            NamedValue::into_insert(&row(&["a"]), "t", InsertMode::Replace).unwrap(),
+            "replace into `t` (`a`) values (?)"
+        );
+        assert_eq!(
+            NamedValue::into_insert(
+                &row(&["a", "b"]),
+                "t",
+                InsertMode::Upsert {
+                    alias: false,
+                    primary: None
+                }
+            )
+            .unwrap(),
+            "insert into `t` (`a`, `b`) values (?, ?) on duplicate key update `a` = values(`a`), `b` = values(`b`)"
+        );
+        assert_eq!(
+            NamedValue::into_insert(
+                &row(&["a", "b"]),
+                "t",
+                InsertMode::Upsert {
+                    alias: true,
+                    primary: None
+                }
+            )
+            .unwrap(),
+            "insert into `t` (`a`, `b`) values (?, ?) as `new` on duplicate key update `a` = `new`.`a`, `b` = `new`.`b`"
+        );
+        assert_eq!(
+            NamedValue::into_insert(
+                &row(&["id", "b"]),
+                "t",
+                InsertMode::Upsert {
+                    alias: false,
+                    primary: Some("id")
+                }
+            )
+            .unwrap(),
+            "insert into `t` (`id`, `b`) values (?, ?) on duplicate key update `b` = values(`b`)"
+        );
+    }
+
+    #[test]
+    fn chunks_by_columns_and_size() {
+        let rows = [
+            row(&["a", "b"]),
+            row(&["a", "b"]),
+            row(&["a"]),
+            row(&["a"]),
+            row(&["a"]),
+        ];
+        assert_eq!(insert_chunks(&rows, 1024), [0..2, 2..5]);
+        // 11 bytes header, 1 byte null bitmap, 6 bytes per parameter
+        assert_eq!(insert_chunks(&rows, 24), [0..1, 1..2, 2..4, 4..5]);
+        assert_eq!(insert_chunks(&rows, 0), [0..1, 1..2, 2..3, 3..4, 4..5]);
+        assert!(insert_chunks(&[], 1024).is_empty());
+    }
+}
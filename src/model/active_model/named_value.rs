@@ -6,6 +6,24 @@ use crate::{
 #[derive(Debug)]
 pub struct NamedValue(pub &'static str, pub Value);
 
+/// How insert statements handle rows with a duplicate key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum InsertMode {
+    /// Fail with a duplicate key error.
+    Insert,
+    /// Skip the row.
+    Ignore,
+    /// Delete the existing row before inserting.
+    Replace,
+    /// Update the inserted columns of the existing row.
+    /// `alias` selects the `as new` syntax (MySQL 8.0.19+) over the deprecated `values()`.
+    /// The `primary` column is not updated.
+    Upsert {
+        alias: bool,
+        primary: Option<&'static str>,
+    },
+}
+
 impl NamedValue {
     pub fn name(&self) -> &'static str {
         self.0
@@ -18,8 +36,23 @@ impl NamedValue {
     pub(super) fn into_insert(
         values: &[NamedValue],
         table: &str,
+        mode: InsertMode,
+    ) -> Result<String, SerializeError> {
+        Self::into_insert_many(values, 1, table, mode)
+    }
+
+    /// Insert statement for `rows` rows with the columns of `values`.
+    pub(super) fn into_insert_many(
+        values: &[NamedValue],
+        rows: usize,
+        table: &str,
+        mode: InsertMode,
     ) -> Result<String, SerializeError> {
-        let mut stmt = String::from("insert into `");
+        let mut stmt = String::from(match mode {
+            InsertMode::Insert | InsertMode::Upsert { .. } => "insert into `",
+            InsertMode::Ignore => "insert ignore into `",
+            InsertMode::Replace => "replace into `",
+        });
         stmt += table;
         stmt += "` (";
         for (i, value) in values.iter().enumerate() {
@@ -30,14 +63,43 @@ impl NamedValue {
             stmt += value.name();
             stmt += "`";
         }
-        stmt += ") values (";
-        for i in 0..values.len() {
-            if i != 0 {
+        stmt += ") values ";
+        for row in 0..rows {
+            if row != 0 {
                 stmt += ", ";
             }
-            stmt += "?";
+            stmt += "(";
+            for i in 0..values.len() {
+                if i != 0 {
+                    stmt += ", ";
+                }
+                stmt += "?";
+            }
+            stmt += ")";
+        }
+        if let InsertMode::Upsert { alias, primary } = mode {
+            if alias {
+                stmt += " as `new`";
+            }
+            stmt += " on duplicate key update ";
+            let values = values.iter().filter(|x| Some(x.name()) != primary);
+            for (i, value) in values.enumerate() {
+                if i != 0 {
+                    stmt += ", ";
+                }
+                stmt += "`";
+                stmt += value.name();
+                if alias {
+                    stmt += "` = `new`.`";
+                    stmt += value.name();
+                    stmt += "`";
+                } else {
+                    stmt += "` = values(`";
+                    stmt += value.name();
+                    stmt += "`)";
+                }
+            }
         }
-        stmt += ")";
         Ok(stmt)
     }
 
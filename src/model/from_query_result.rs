@@ -1,16 +1,22 @@
 use crate::{connection::types::Column, error::ParseError, types::Value};
 
-pub trait FromQueryResultMapping<ModelData: super::ModelData>: Default {
+pub trait FromQueryResultMapping<Row>: Default {
     fn set_mapping_inner(&mut self, column: &Column, table: &str, index: usize);
 
+    /// Returns `true` if all mapped columns of the row are `NULL`, or no column is mapped.
+    ///
+    /// A `left join` without a matching row results in `NULL` columns.
+    fn all_null(&self, row: &[Value]) -> bool {
+        let _ = row;
+        false
+    }
+
+    /// Maps a column of the result set, whose table is the alias of the query.
+    ///
+    /// Mappings of models strip [`ModelData::TABLE_WITH_POINT`](super::ModelData::TABLE_WITH_POINT)
+    /// before passing the table to [`set_mapping_inner`](Self::set_mapping_inner).
     fn set_mapping(&mut self, column: &Column, table: &str, index: usize) {
-        self.set_mapping_inner(
-            column,
-            table
-                .strip_prefix(ModelData::TABLE_WITH_POINT)
-                .unwrap_or(table),
-            index,
-        )
+        self.set_mapping_inner(column, table, index)
     }
 
     fn from_columns(columns: &[Column]) -> Self {
@@ -22,7 +28,12 @@ pub trait FromQueryResultMapping<ModelData: super::ModelData>: Default {
     }
 }
 
-pub trait FromQueryResult: super::ModelData + Sized {
+/// Row of a result set.
+///
+/// This is implemented by the derive macro for models, and for `Vec<Value>`, `HashMap<String, Value>`
+/// (keyed by the column names) and tuples of up to 16 values. Scalars of the crate (numbers, strings,
+/// date and time types and the types behind features) and their `Option` are read from rows with a single column.
+pub trait FromQueryResult: Sized {
     type Mapping: FromQueryResultMapping<Self>;
 
     fn from_mapping_and_row(
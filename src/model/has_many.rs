@@ -0,0 +1,146 @@
+use {
+    super::{FromQueryResult, FromQueryResultMapping, IntoQuery, Model},
+    crate::{
+        error::{ParseError, RuntimeError},
+        types::Value,
+        Connection, Error,
+    },
+    std::{collections::HashMap, hash::Hash, ops::Range},
+};
+
+/// One-to-many relation, where the rows of `C` reference `P` by a foreign key.
+///
+/// The accessors are generated by the `IntoQuery` derive macro for fields with
+/// `#[mysql_connector(has_many = "<foreign key>")]`, e.g. `User::addresses()`.
+pub struct HasMany<P: Model, C> {
+    /// Column of the child table, which contains the primary key of the parent.
+    foreign_key: &'static str,
+    children: fn(&mut P) -> &mut Vec<C>,
+}
+
+impl<P: Model, C> HasMany<P, C> {
+    pub fn new(foreign_key: &'static str, children: fn(&mut P) -> &mut Vec<C>) -> Self {
+        Self {
+            foreign_key,
+            children,
+        }
+    }
+
+    pub fn foreign_key(&self) -> &'static str {
+        self.foreign_key
+    }
+}
+
+impl<P, C> HasMany<P, C>
+where
+    P: Model,
+    P::Primary: Eq + Hash,
+    C: IntoQuery + FromQueryResult,
+    Value: TryInto<P::Primary>,
+    <Value as TryInto<P::Primary>>::Error: Into<ParseError>,
+{
+    /// Loads the children of all parents with `where <foreign key> in (...)` queries.
+    pub async fn load(&self, parents: &mut [P], conn: &mut Connection) -> Result<(), Error> {
+        let keys = parents
+            .iter()
+            .map(|x| x.primary().into())
+            .collect::<Vec<Value>>();
+        let query_len = self.query(1)?.len();
+        let mut children = Vec::new();
+        for chunk in key_chunks(&keys, query_len, conn.data().max_allowed_packet()) {
+            let chunk = &keys[chunk];
+            let query = self.query(chunk.len())?;
+            let mut stmt = conn.prepare_statement(&query).await?;
+            let mut result = stmt.query::<_, Vec<Value>>(chunk).await?;
+            let mapping = C::Mapping::from_columns(result.columns());
+            while let Some(mut row) = result.next().await? {
+                let key = row[0].clone().try_into().map_err(Into::into)?;
+                children.push((key, C::from_mapping_and_row(&mapping, &mut row)?));
+            }
+        }
+        self.stitch(parents, children);
+        Ok(())
+    }
+
+    /// Select query of the children, whose first column is the foreign key.
+    pub(super) fn query(&self, keys: usize) -> Result<String, Error> {
+        let select = C::build_query();
+        let Some(select) = select.strip_prefix("select ") else {
+            return Err(RuntimeError::InvalidModelQuery(select).into());
+        };
+        let mut query = String::from("select `");
+        query += C::TABLE;
+        query += "`.`";
+        query += self.foreign_key;
+        query += "`, ";
+        query += select;
+        query += " where `";
+        query += C::TABLE;
+        query += "`.`";
+        query += self.foreign_key;
+        query += "` in (?";
+        query += &", ?".repeat(keys.saturating_sub(1));
+        query += ")";
+        Ok(query)
+    }
+
+    /// Appends the children to the parents with the same primary key.
+    pub(super) fn stitch(&self, parents: &mut [P], children: Vec<(P::Primary, C)>) {
+        let mut parents = parents
+            .iter_mut()
+            .map(|x| (x.primary(), x))
+            .collect::<HashMap<_, _>>();
+        for (key, child) in children {
+            if let Some(parent) = parents.get_mut(&key) {
+                (self.children)(parent).push(child);
+            }
+        }
+    }
+}
+
+/// Splits the keys into runs that can be loaded by one query.
+///
+/// A run has at most `u16::MAX` keys, and both the query, which is `query_len` bytes long for one key,
+/// and the execute packet fit into `max_len` bytes.
+fn key_chunks(keys: &[Value], query_len: usize, max_len: usize) -> Vec<Range<usize>> {
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    let mut len = 0;
+    for (i, key) in keys.iter().enumerate() {
+        let key_len = 2 + key.bin_len() as usize;
+        let params = i - start + 1;
+        let packet_len = 11 + params.div_ceil(8) + len + key_len;
+        // every further key adds `, ?` to the query
+        let prepare_len = 1 + query_len + 3 * (params - 1);
+        if i != start
+            && (params > u16::MAX as usize || packet_len > max_len || prepare_len > max_len)
+        {
+            chunks.push(start..i);
+            start = i;
+            len = 0;
+        }
+        len += key_len;
+    }
+    if start < keys.len() {
+        chunks.push(start..keys.len());
+    }
+    chunks
+}
+
+#[cfg(test)]
+mod test {
+    use {super::key_chunks, crate::types::Value};
+
+    #[test]
+    #[allow(clippy::single_range_in_vec_init)]
+    fn chunks_by_size() {
+        let keys = (0..5).map(Value::UInt).collect::<Vec<_>>();
+        assert_eq!(key_chunks(&keys, 10, 1024), [0..5]);
+        // an execute packet with three keys has 11 + 1 + 3 * 6 bytes
+        assert_eq!(key_chunks(&keys, 10, 30), [0..3, 3..5]);
+        // the prepare packet with three keys has 1 + 100 + 2 * 3 bytes
+        assert_eq!(key_chunks(&keys, 100, 106), [0..2, 2..4, 4..5]);
+        assert_eq!(key_chunks(&keys, 10, 0), [0..1, 1..2, 2..3, 3..4, 4..5]);
+        assert!(key_chunks(&[], 10, 1024).is_empty());
+    }
+}
@@ -1,4 +1,4 @@
-use super::ModelData;
+use super::{FromQueryResult, ModelData, Select};
 
 macro_rules! append {
     ($str: expr; $($part:expr),* $(,)?) => {
@@ -18,24 +18,74 @@ pub struct QueryColumnReference {
     pub table: &'static str,
     /// Referenced key
     pub key: &'static str,
-    /// Columns of referenced table
-    pub columns: &'static [QueryColumn],
+    /// Columns of referenced table.
+    ///
+    /// This is a function, so models can reference themselves.
+    pub columns: fn() -> &'static [QueryColumn],
+    /// The reference may be `NULL`, so the table is joined with `left join`.
+    pub optional: bool,
+    /// Maximum number of times the referenced table occurs along a chain of references,
+    /// which ends the recursion of self-referential models.
+    ///
+    /// Beyond it only the column is selected, so a reference that isn't loaded can be told apart from `NULL`.
+    pub depth: Option<usize>,
 }
 
 impl QueryColumnReference {
     /// Join the referenced table and append all columns to the select,
     /// which may include some more joins.
-    fn join(&self, namespace: &str, select: &mut String, join: &mut String) {
+    ///
+    /// Returns `false` if no column was appended.
+    /// `path` contains the tables of the parent references. The reference is joined with
+    /// `left join` if it or one of its parents is optional, as an inner join would drop the row.
+    fn join(
+        &self,
+        namespace: &str,
+        left: bool,
+        path: &mut Vec<&'static str>,
+        select: &mut String,
+        join: &mut String,
+    ) -> bool {
+        if let Some(depth) = self.depth {
+            if path.iter().filter(|x| **x == self.table).count() >= depth {
+                append!(*select; "`", namespace, "`.`", self.column, "`");
+                return true;
+            }
+        }
+        let left = left || self.optional;
         let new_namespace = namespace.to_owned() + "." + self.column;
-        append!(*join; " join `", self.table, "` as `", &new_namespace, "` on (`", namespace, "`.`", self.column, "` = `", &new_namespace, "`.`", self.key, "`)");
+        let keyword = if left { " left join `" } else { " join `" };
+        append!(*join; keyword, self.table, "` as `", &new_namespace, "` on (`", namespace, "`.`", self.column, "` = `", &new_namespace, "`.`", self.key, "`)");
 
-        for (i, column) in self.columns.iter().enumerate() {
-            if i != 0 {
-                *select += ", ";
-            }
-            column.append_to_select(&new_namespace, select, join);
+        path.push(self.table);
+        let appended = append_columns((self.columns)(), &new_namespace, left, path, select, join);
+        path.pop();
+        appended
+    }
+}
+
+/// Appends the columns separated by commas. Returns `false` if no column was appended.
+fn append_columns(
+    columns: &[QueryColumn],
+    namespace: &str,
+    left: bool,
+    path: &mut Vec<&'static str>,
+    select: &mut String,
+    join: &mut String,
+) -> bool {
+    let mut appended = false;
+    for column in columns {
+        let len = select.len();
+        if appended {
+            *select += ", ";
+        }
+        if column.append_to_select(namespace, left, path, select, join) {
+            appended = true;
+        } else {
+            select.truncate(len);
         }
     }
+    appended
 }
 
 pub enum QueryColumn {
@@ -44,14 +94,20 @@ pub enum QueryColumn {
 }
 
 impl QueryColumn {
-    fn append_to_select(&self, namespace: &str, select: &mut String, join: &mut String) {
+    fn append_to_select(
+        &self,
+        namespace: &str,
+        left: bool,
+        path: &mut Vec<&'static str>,
+        select: &mut String,
+        join: &mut String,
+    ) -> bool {
         match self {
             Self::Column(column) => {
                 append!(*select; "`", namespace, "`.`", *column, "`");
+                true
             }
-            Self::Reference(r#struct) => {
-                r#struct.join(namespace, select, join);
-            }
+            Self::Reference(r#struct) => r#struct.join(namespace, left, path, select, join),
         }
     }
 }
@@ -62,14 +118,25 @@ pub trait IntoQuery: ModelData {
     fn build_query() -> String {
         let mut select = String::from("select ");
         let mut join = String::new();
-        for (i, column) in Self::COLUMNS.iter().enumerate() {
-            if i != 0 {
-                select += ", ";
-            }
-            column.append_to_select(Self::TABLE, &mut select, &mut join);
-        }
+        let mut path = vec![Self::TABLE];
+        append_columns(
+            Self::COLUMNS,
+            Self::TABLE,
+            false,
+            &mut path,
+            &mut select,
+            &mut join,
+        );
         append!(select; " from `", Self::TABLE, "`");
         select += &join;
         select
     }
+
+    /// Builds a select query with conditions, ordering and limit.
+    fn select() -> Select<Self>
+    where
+        Self: FromQueryResult,
+    {
+        Select::default()
+    }
 }
@@ -1,19 +1,26 @@
 mod active_model;
 mod from_query_result;
+mod has_many;
 mod into_query;
 mod plain;
+mod select;
 
 #[cfg(test)]
 mod test;
 
-use crate::types::Value;
+use crate::{types::Value, Connection, Error};
+
+pub(crate) use plain::{impl_from_query_result, single_column};
 
 pub use {
     active_model::{
         ActiveModel, ActiveReference, ActiveValue, HasActiveModel, NamedValue, UpdateModel,
     },
     from_query_result::{FromQueryResult, FromQueryResultMapping},
+    has_many::HasMany,
     into_query::{IntoQuery, QueryColumn, QueryColumnReference},
+    plain::EmptyMapping,
+    select::{Condition, ModelColumn, Order, Select},
 };
 
 pub trait ModelData: std::fmt::Debug + Sized {
@@ -21,6 +28,7 @@ pub trait ModelData: std::fmt::Debug + Sized {
     const TABLE_WITH_POINT: &'static str;
 }
 
+#[allow(async_fn_in_trait)]
 pub trait Model: ModelData + HasActiveModel {
     const PRIMARY: &'static str;
     const AUTO_INCREMENT: bool;
@@ -36,4 +44,50 @@ pub trait Model: ModelData + HasActiveModel {
     fn update_model(&self) -> UpdateModel<Self> {
         UpdateModel::new(self.primary())
     }
+
+    /// Loads the model with the primary key `id`.
+    async fn find_by_primary(
+        conn: &mut Connection,
+        id: Self::Primary,
+    ) -> Result<Option<Self>, Error>
+    where
+        Self: IntoQuery + FromQueryResult,
+    {
+        let query = find_by_primary_query::<Self>();
+        let mut stmt = conn.prepare_statement(&query).await?;
+        let mut result = stmt.query::<_, Self>(&[id.into()]).await?;
+        result.one().await
+    }
+
+    /// Deletes the row of the model. Returns `true` if a row was deleted.
+    async fn delete(self, conn: &mut Connection) -> Result<bool, Error> {
+        Self::delete_by_primary(conn, self.primary()).await
+    }
+
+    /// Deletes the row with the primary key `id`. Returns `true` if a row was deleted.
+    async fn delete_by_primary(conn: &mut Connection, id: Self::Primary) -> Result<bool, Error> {
+        let query = delete_by_primary_query::<Self>();
+        let mut stmt = conn.prepare_statement(&query).await?;
+        let ok = stmt.execute(&[id.into()]).await?;
+        Ok(ok.affected_rows() != 0)
+    }
+}
+
+fn find_by_primary_query<M: Model + IntoQuery>() -> String {
+    let mut query = M::build_query();
+    query += " where `";
+    query += M::TABLE;
+    query += "`.`";
+    query += M::PRIMARY;
+    query += "` = ?";
+    query
+}
+
+fn delete_by_primary_query<M: Model>() -> String {
+    let mut query = String::from("delete from `");
+    query += M::TABLE;
+    query += "` where `";
+    query += M::PRIMARY;
+    query += "` = ?";
+    query
 }
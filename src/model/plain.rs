@@ -1,6 +1,8 @@
 use {
     super::{FromQueryResult, FromQueryResultMapping},
     crate::{connection::types::Column, error::ParseError, types::Value},
+    chrono::{DateTime, Duration, FixedOffset, NaiveDate, NaiveDateTime, Utc},
+    std::collections::HashMap,
 };
 
 impl super::ModelData for Vec<Value> {
@@ -22,6 +24,7 @@ impl FromQueryResult for Vec<Value> {
     }
 }
 
+/// Mapping of rows which are read by the position of the columns, so it only counts them.
 #[derive(Default)]
 pub struct EmptyMapping(usize);
 
@@ -29,13 +32,21 @@ impl EmptyMapping {
     pub fn len(&self) -> usize {
         self.0
     }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
 }
 
-impl FromQueryResultMapping<Vec<Value>> for EmptyMapping {
+impl<Row> FromQueryResultMapping<Row> for EmptyMapping {
     fn set_mapping_inner(&mut self, _column: &Column, _name: &str, _index: usize) {
         self.0 += 1;
     }
 
+    fn all_null(&self, row: &[Value]) -> bool {
+        row.iter().all(|x| matches!(x, Value::Null))
+    }
+
     fn set_mapping(&mut self, _column: &Column, _name: &str, _index: usize) {
         self.0 += 1;
     }
@@ -44,3 +55,162 @@ impl FromQueryResultMapping<Vec<Value>> for EmptyMapping {
         Self(columns.len())
     }
 }
+
+fn take<T>(value: &mut Value) -> Result<T, ParseError>
+where
+    Value: TryInto<T>,
+    <Value as TryInto<T>>::Error: Into<ParseError>,
+{
+    value.take().try_into().map_err(Into::into)
+}
+
+/// Reads a row with a single column.
+pub(crate) fn single_column<T>(mapping: &EmptyMapping, row: &mut [Value]) -> Result<T, ParseError>
+where
+    Value: TryInto<T>,
+    <Value as TryInto<T>>::Error: Into<ParseError>,
+{
+    if mapping.len() != 1 || row.len() != 1 {
+        return Err(ParseError::RowLengthMismatch);
+    }
+    take(&mut row[0])
+}
+
+/// Implements [`FromQueryResult`] for scalars and their `Option`, which are read from rows with a single column.
+macro_rules! impl_from_query_result {
+    (impl<$($g:ident),+> $t:ty) => {
+        impl<$($g),+> $crate::model::FromQueryResult for $t
+        where
+            $crate::types::Value: TryInto<$t>,
+            <$crate::types::Value as TryInto<$t>>::Error: Into<$crate::error::ParseError>,
+        {
+            type Mapping = $crate::model::EmptyMapping;
+
+            fn from_mapping_and_row(
+                mapping: &Self::Mapping,
+                row: &mut Vec<$crate::types::Value>,
+            ) -> std::result::Result<Self, $crate::error::ParseError> {
+                $crate::model::single_column(mapping, row)
+            }
+        }
+
+        impl<$($g),+> $crate::model::FromQueryResult for Option<$t>
+        where
+            $crate::types::Value: TryInto<Option<$t>>,
+            <$crate::types::Value as TryInto<Option<$t>>>::Error: Into<$crate::error::ParseError>,
+        {
+            type Mapping = $crate::model::EmptyMapping;
+
+            fn from_mapping_and_row(
+                mapping: &Self::Mapping,
+                row: &mut Vec<$crate::types::Value>,
+            ) -> std::result::Result<Self, $crate::error::ParseError> {
+                $crate::model::single_column(mapping, row)
+            }
+        }
+    };
+    ($($t:ty),+ $(,)?) => {
+        $(
+            impl $crate::model::FromQueryResult for $t {
+                type Mapping = $crate::model::EmptyMapping;
+
+                fn from_mapping_and_row(
+                    mapping: &Self::Mapping,
+                    row: &mut Vec<$crate::types::Value>,
+                ) -> std::result::Result<Self, $crate::error::ParseError> {
+                    $crate::model::single_column(mapping, row)
+                }
+            }
+
+            impl $crate::model::FromQueryResult for Option<$t> {
+                type Mapping = $crate::model::EmptyMapping;
+
+                fn from_mapping_and_row(
+                    mapping: &Self::Mapping,
+                    row: &mut Vec<$crate::types::Value>,
+                ) -> std::result::Result<Self, $crate::error::ParseError> {
+                    $crate::model::single_column(mapping, row)
+                }
+            }
+        )+
+    };
+}
+pub(crate) use impl_from_query_result;
+
+impl_from_query_result!(i8, i16, i32, i64, u8, u16, u32, u64, f32, f64, bool);
+impl_from_query_result!(String, Vec<u8>);
+impl_from_query_result!(NaiveDate, NaiveDateTime, Duration);
+impl_from_query_result!(DateTime<Utc>, DateTime<FixedOffset>);
+#[cfg(feature = "decimal")]
+impl_from_query_result!(rust_decimal::Decimal);
+
+/// Tuples are mapped by the position of the columns.
+macro_rules! impl_tuple {
+    ($len:literal; $($t:ident),+) => {
+        impl<$($t),+> FromQueryResult for ($($t,)+)
+        where
+            $(
+                Value: TryInto<$t>,
+                <Value as TryInto<$t>>::Error: Into<ParseError>,
+            )+
+        {
+            type Mapping = EmptyMapping;
+
+            fn from_mapping_and_row(
+                mapping: &Self::Mapping,
+                row: &mut Vec<Value>,
+            ) -> std::result::Result<Self, ParseError> {
+                if mapping.len() != $len || row.len() != $len {
+                    return Err(ParseError::RowLengthMismatch);
+                }
+                let mut row = row.iter_mut();
+                Ok(($(take::<$t>(row.next().unwrap())?,)+))
+            }
+        }
+    };
+}
+
+impl_tuple!(1; A);
+impl_tuple!(2; A, B);
+impl_tuple!(3; A, B, C);
+impl_tuple!(4; A, B, C, D);
+impl_tuple!(5; A, B, C, D, E);
+impl_tuple!(6; A, B, C, D, E, F);
+impl_tuple!(7; A, B, C, D, E, F, G);
+impl_tuple!(8; A, B, C, D, E, F, G, H);
+impl_tuple!(9; A, B, C, D, E, F, G, H, I);
+impl_tuple!(10; A, B, C, D, E, F, G, H, I, J);
+impl_tuple!(11; A, B, C, D, E, F, G, H, I, J, K);
+impl_tuple!(12; A, B, C, D, E, F, G, H, I, J, K, L);
+impl_tuple!(13; A, B, C, D, E, F, G, H, I, J, K, L, M);
+impl_tuple!(14; A, B, C, D, E, F, G, H, I, J, K, L, M, N);
+impl_tuple!(15; A, B, C, D, E, F, G, H, I, J, K, L, M, N, O);
+impl_tuple!(16; A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P);
+
+/// Row keyed by the column names (or aliases).
+impl FromQueryResult for HashMap<String, Value> {
+    type Mapping = ColumnNameMapping;
+
+    fn from_mapping_and_row(
+        mapping: &Self::Mapping,
+        row: &mut Vec<Value>,
+    ) -> std::result::Result<Self, ParseError> {
+        if row.len() != mapping.0.len() {
+            return Err(ParseError::RowLengthMismatch);
+        }
+        Ok(mapping.0.iter().cloned().zip(row.drain(..)).collect())
+    }
+}
+
+#[derive(Default)]
+pub struct ColumnNameMapping(Vec<String>);
+
+impl FromQueryResultMapping<HashMap<String, Value>> for ColumnNameMapping {
+    fn set_mapping_inner(&mut self, column: &Column, _name: &str, _index: usize) {
+        self.0.push(column.name().to_owned());
+    }
+
+    fn all_null(&self, row: &[Value]) -> bool {
+        row.iter().all(|x| matches!(x, Value::Null))
+    }
+}
@@ -0,0 +1,297 @@
+use {
+    super::{FromQueryResult, IntoQuery},
+    crate::{error::SerializeError, types::Value, Connection, Error},
+    std::{marker::PhantomData, ops},
+};
+
+/// Column of a model, as selected by [`IntoQuery::build_query`].
+///
+/// The accessors are generated by the `IntoQuery` derive macro, e.g. `User::name()`.
+#[derive(Debug, Clone)]
+pub struct ModelColumn<T> {
+    /// Table or alias of the joined table, e.g. `entity.map`.
+    namespace: String,
+    name: &'static str,
+    r#type: PhantomData<fn() -> T>,
+}
+
+impl<T> ModelColumn<T> {
+    pub fn new(namespace: &str, name: &'static str) -> Self {
+        Self {
+            namespace: namespace.to_owned(),
+            name,
+            r#type: PhantomData,
+        }
+    }
+
+    /// Column of a referenced model, joined through `reference`.
+    ///
+    /// `Map::name().via(Entity::map())` is the column `entity.map`.`name`.
+    pub fn via<R>(mut self, reference: ModelColumn<R>) -> Self {
+        self.namespace = reference.namespace + "." + reference.name;
+        self
+    }
+
+    fn sql(&self) -> String {
+        format!("`{}`.`{}`", self.namespace, self.name)
+    }
+
+    fn compare<V>(self, operator: &str, value: V) -> Condition
+    where
+        V: Into<T>,
+        T: TryInto<Value>,
+        <T as TryInto<Value>>::Error: Into<SerializeError>,
+    {
+        let mut condition = Condition::new(format!("{} {operator} ?", self.sql()));
+        condition.bind(value.into());
+        condition
+    }
+
+    pub fn eq<V>(self, value: V) -> Condition
+    where
+        V: Into<T>,
+        T: TryInto<Value>,
+        <T as TryInto<Value>>::Error: Into<SerializeError>,
+    {
+        self.compare("=", value)
+    }
+
+    pub fn ne<V>(self, value: V) -> Condition
+    where
+        V: Into<T>,
+        T: TryInto<Value>,
+        <T as TryInto<Value>>::Error: Into<SerializeError>,
+    {
+        self.compare("<>", value)
+    }
+
+    pub fn lt<V>(self, value: V) -> Condition
+    where
+        V: Into<T>,
+        T: TryInto<Value>,
+        <T as TryInto<Value>>::Error: Into<SerializeError>,
+    {
+        self.compare("<", value)
+    }
+
+    pub fn le<V>(self, value: V) -> Condition
+    where
+        V: Into<T>,
+        T: TryInto<Value>,
+        <T as TryInto<Value>>::Error: Into<SerializeError>,
+    {
+        self.compare("<=", value)
+    }
+
+    pub fn gt<V>(self, value: V) -> Condition
+    where
+        V: Into<T>,
+        T: TryInto<Value>,
+        <T as TryInto<Value>>::Error: Into<SerializeError>,
+    {
+        self.compare(">", value)
+    }
+
+    pub fn ge<V>(self, value: V) -> Condition
+    where
+        V: Into<T>,
+        T: TryInto<Value>,
+        <T as TryInto<Value>>::Error: Into<SerializeError>,
+    {
+        self.compare(">=", value)
+    }
+
+    /// Matches the pattern, using `%` and `_` as wildcards.
+    pub fn like(self, pattern: impl Into<String>) -> Condition {
+        let mut condition = Condition::new(format!("{} like ?", self.sql()));
+        condition.bind(pattern.into());
+        condition
+    }
+
+    /// Matches any of the values. An empty list matches no rows.
+    pub fn is_in<V, I>(self, values: I) -> Condition
+    where
+        I: IntoIterator<Item = V>,
+        V: Into<T>,
+        T: TryInto<Value>,
+        <T as TryInto<Value>>::Error: Into<SerializeError>,
+    {
+        let mut condition = Condition::new(String::new());
+        for value in values {
+            condition.bind(value.into());
+        }
+        condition.sql = match condition.params.len() {
+            0 => String::from("false"),
+            len => format!("{} in (?{})", self.sql(), ", ?".repeat(len - 1)),
+        };
+        condition
+    }
+
+    pub fn is_null(self) -> Condition {
+        Condition::new(format!("{} is null", self.sql()))
+    }
+
+    pub fn is_not_null(self) -> Condition {
+        Condition::new(format!("{} is not null", self.sql()))
+    }
+
+    pub fn asc(self) -> Order {
+        Order(self.sql() + " asc")
+    }
+
+    pub fn desc(self) -> Order {
+        Order(self.sql() + " desc")
+    }
+}
+
+/// Condition of a `where` clause. The values are bound as parameters of a prepared statement.
+///
+/// Conditions are combined with [`and`](Self::and) and [`or`](Self::or), and negated with `!`.
+#[derive(Debug)]
+pub struct Condition {
+    sql: String,
+    params: Vec<Value>,
+    /// First value that couldn't be converted, returned when the query is built.
+    error: Option<SerializeError>,
+}
+
+impl Condition {
+    fn new(sql: String) -> Self {
+        Self {
+            sql,
+            params: Vec::new(),
+            error: None,
+        }
+    }
+
+    fn bind<T>(&mut self, value: T)
+    where
+        T: TryInto<Value>,
+        <T as TryInto<Value>>::Error: Into<SerializeError>,
+    {
+        match value.try_into() {
+            Ok(value) => self.params.push(value),
+            Err(err) => {
+                self.error.get_or_insert(err.into());
+            }
+        }
+    }
+
+    fn combine(mut self, operator: &str, other: Condition) -> Self {
+        self.sql = format!("({}) {operator} ({})", self.sql, other.sql);
+        self.params.extend(other.params);
+        self.error = self.error.or(other.error);
+        self
+    }
+
+    pub fn and(self, other: Condition) -> Self {
+        self.combine("and", other)
+    }
+
+    pub fn or(self, other: Condition) -> Self {
+        self.combine("or", other)
+    }
+}
+
+impl ops::Not for Condition {
+    type Output = Self;
+
+    fn not(mut self) -> Self::Output {
+        self.sql = format!("not ({})", self.sql);
+        self
+    }
+}
+
+/// Ordering of an `order by` clause, created by [`ModelColumn::asc`] and [`ModelColumn::desc`].
+#[derive(Debug, Clone)]
+pub struct Order(String);
+
+/// Select query of a model, created by [`IntoQuery::select`].
+#[derive(Debug)]
+pub struct Select<M: IntoQuery + FromQueryResult> {
+    condition: Option<Condition>,
+    order: Vec<Order>,
+    limit: Option<u64>,
+    offset: Option<u64>,
+    model: PhantomData<fn() -> M>,
+}
+
+impl<M: IntoQuery + FromQueryResult> Default for Select<M> {
+    fn default() -> Self {
+        Self {
+            condition: None,
+            order: Vec::new(),
+            limit: None,
+            offset: None,
+            model: PhantomData,
+        }
+    }
+}
+
+impl<M: IntoQuery + FromQueryResult> Select<M> {
+    /// Adds a condition. Multiple conditions are combined with `and`.
+    pub fn filter(mut self, condition: Condition) -> Self {
+        self.condition = Some(match self.condition {
+            Some(x) => x.and(condition),
+            None => condition,
+        });
+        self
+    }
+
+    pub fn order_by(mut self, order: Order) -> Self {
+        self.order.push(order);
+        self
+    }
+
+    pub fn limit(mut self, limit: u64) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn offset(mut self, offset: u64) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Builds the query and the values of its parameters.
+    pub fn build(self) -> Result<(String, Vec<Value>), SerializeError> {
+        let mut query = M::build_query();
+        let mut params = Vec::new();
+        if let Some(condition) = self.condition {
+            if let Some(err) = condition.error {
+                return Err(err);
+            }
+            query += " where ";
+            query += &condition.sql;
+            params = condition.params;
+        }
+        for (i, order) in self.order.iter().enumerate() {
+            query += if i == 0 { " order by " } else { ", " };
+            query += &order.0;
+        }
+        if self.limit.is_some() || self.offset.is_some() {
+            // `offset` is only valid after `limit`
+            query += " limit ?";
+            params.push(Value::ULong(self.limit.unwrap_or(u64::MAX)));
+        }
+        if let Some(offset) = self.offset {
+            query += " offset ?";
+            params.push(Value::ULong(offset));
+        }
+        Ok((query, params))
+    }
+
+    pub async fn fetch_all(self, conn: &mut Connection) -> Result<Vec<M>, Error> {
+        let (query, params) = self.build()?;
+        let mut stmt = conn.prepare_statement(&query).await?;
+        let mut result = stmt.query::<_, M>(&params).await?;
+        result.collect().await
+    }
+
+    pub async fn fetch_one(self, conn: &mut Connection) -> Result<Option<M>, Error> {
+        let (query, params) = self.limit(1).build()?;
+        let mut stmt = conn.prepare_statement(&query).await?;
+        let mut result = stmt.query::<_, M>(&params).await?;
+        result.one().await
+    }
+}
@@ -1,6 +1,15 @@
 use {
-    crate::{self as mysql_connector, macros::*, model::IntoQuery},
+    super::{delete_by_primary_query, find_by_primary_query},
+    crate::{
+        self as mysql_connector,
+        connection::types::Column,
+        error::ParseError,
+        macros::*,
+        model::{FromQueryResult, FromQueryResultMapping, IntoQuery, Model},
+        types::{MysqlEnum, Value},
+    },
     chrono::{Duration, NaiveDateTime},
+    std::collections::{HashMap, HashSet},
 };
 
 #[derive(Debug)]
@@ -33,6 +42,111 @@ struct Map {
     last_played: Option<NaiveDateTime>,
 }
 
+#[allow(dead_code)]
+#[derive(Debug, ModelData, FromQueryResult, ActiveModel, IntoQuery, Model)]
+#[mysql_connector(table = "region", primary = "id", auto_increment = "true")]
+struct Region {
+    id: u32,
+    name: String,
+    #[mysql_connector(depth = 2)]
+    parent: Option<Box<Region>>,
+    capital: Option<Map>,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, ModelData, FromQueryResult, ActiveModel, IntoQuery, Model)]
+#[mysql_connector(table = "user", primary = "id", auto_increment = "true")]
+struct User {
+    id: u32,
+    name: String,
+    #[mysql_connector(has_many = "user")]
+    addresses: Vec<Address>,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, ModelData, FromQueryResult, ActiveModel, IntoQuery, Model)]
+#[mysql_connector(table = "address", primary = "id", auto_increment = "true")]
+struct Address {
+    id: u32,
+    street: String,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, MysqlEnum)]
+#[mysql_connector(rename_all = "snake_case")]
+enum Permission {
+    Read,
+    Write,
+    #[mysql_connector(rename = "admin")]
+    Administrate,
+    ChangeOwner,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, ModelData, FromQueryResult, ActiveModel, IntoQuery, Model)]
+#[mysql_connector(table = "member", primary = "id", auto_increment = "true")]
+struct Member {
+    id: u32,
+    #[mysql_connector(value)]
+    role: Permission,
+    #[mysql_connector(value)]
+    fallback: Option<Permission>,
+    permissions: HashSet<Permission>,
+}
+
+mod yes_no {
+    use crate::{
+        error::{ParseError, SerializeError},
+        types::{Value, ValueType},
+    };
+
+    pub fn from_value(value: Value) -> Result<bool, ParseError> {
+        match value {
+            Value::Bytes(x) if x == b"Y" => Ok(true),
+            Value::Bytes(x) if x == b"N" => Ok(false),
+            x => Err(ParseError::wrong_value(ValueType::Bytes, x)),
+        }
+    }
+
+    pub fn into_value(value: bool) -> Result<Value, SerializeError> {
+        Ok(Value::Bytes(if value { b"Y" } else { b"N" }.to_vec()))
+    }
+}
+
+fn default_score() -> u32 {
+    100
+}
+
+#[allow(dead_code)]
+#[derive(Debug, ModelData, FromQueryResult, ActiveModel, IntoQuery, Model)]
+#[mysql_connector(table = "account", primary = "id", auto_increment = "true", ty = Vector3{x,y,z})]
+struct Account {
+    #[mysql_connector(rename = "accountId")]
+    id: u32,
+    #[mysql_connector(rename = "userName")]
+    user_name: String,
+    #[mysql_connector(with = "yes_no")]
+    active: bool,
+    #[mysql_connector(default)]
+    nickname: Option<String>,
+    #[mysql_connector(default = "default_score")]
+    score: u32,
+    #[mysql_connector(skip)]
+    cached: Vec<u8>,
+    #[mysql_connector(rename = "pos")]
+    position: Vector3,
+    #[mysql_connector(rename = "homeMap")]
+    map: Option<Map>,
+}
+
+#[cfg(feature = "uuid")]
+#[derive(Debug, ModelData, FromQueryResult, ActiveModel, IntoQuery, Model)]
+#[mysql_connector(table = "session", primary = "id", auto_increment = "false")]
+pub struct Session {
+    id: mysql_connector::types::SwappedUuid,
+    token: uuid::Uuid,
+    revoked: Option<uuid::Uuid>,
+}
+
 #[test]
 fn test_into_query() {
     assert_eq!(
@@ -49,3 +163,365 @@ fn test_into_query() {
         ),
     );
 }
+
+#[test]
+fn test_primary_queries() {
+    assert_eq!(
+        find_by_primary_query::<Map>(),
+        "select `map`.`id`, `map`.`name`, `map`.`difficulty`, `map`.`last_played` from `map` where `map`.`id` = ?"
+    );
+    assert_eq!(
+        delete_by_primary_query::<Map>(),
+        "delete from `map` where `id` = ?"
+    );
+}
+
+#[test]
+fn test_select() {
+    let (query, params) = Entity::select()
+        .filter(Entity::name().eq("foo").or(Entity::heading().gt(1.5)))
+        .filter(Map::name().via(Entity::map()).like("a%"))
+        .filter(Entity::position_x().is_in([1, 2]))
+        .order_by(Entity::id().desc())
+        .order_by(Entity::map().asc())
+        .limit(10)
+        .offset(20)
+        .build()
+        .unwrap();
+    assert_eq!(
+        query,
+        Entity::build_query()
+            + concat!(
+                " where (((`entity`.`name` = ?) or (`entity`.`heading` > ?)) and (`entity.map`.`name` like ?))",
+                " and (`entity`.`position_x` in (?, ?))",
+                " order by `entity`.`id` desc, `entity`.`map` asc limit ? offset ?",
+            )
+    );
+    assert_eq!(
+        params,
+        [
+            Value::Bytes(b"foo".to_vec()),
+            Value::Float(1.5),
+            Value::Bytes(b"a%".to_vec()),
+            Value::Int(1),
+            Value::Int(2),
+            Value::ULong(10),
+            Value::ULong(20),
+        ]
+    );
+
+    let (query, params) = Map::select()
+        .filter(!Map::last_played().is_null())
+        .filter(Map::id().is_in(Vec::<u32>::new()))
+        .build()
+        .unwrap();
+    assert_eq!(
+        query,
+        Map::build_query() + " where (not (`map`.`last_played` is null)) and (false)"
+    );
+    assert!(params.is_empty());
+}
+
+#[test]
+fn test_optional_references() {
+    let map_columns =
+        |x: &str| format!("`{x}`.`id`, `{x}`.`name`, `{x}`.`difficulty`, `{x}`.`last_played`");
+    // the parent of the parent isn't joined, as the table would occur three times, only its key is selected
+    assert_eq!(
+        Region::build_query(),
+        format!(
+            "select `region`.`id`, `region`.`name`, `region.parent`.`id`, `region.parent`.`name`, `region.parent`.`parent`, {}, {} from `region`{}{}{}",
+            map_columns("region.parent.capital"),
+            map_columns("region.capital"),
+            " left join `region` as `region.parent` on (`region`.`parent` = `region.parent`.`id`)",
+            " left join `map` as `region.parent.capital` on (`region.parent`.`capital` = `region.parent.capital`.`id`)",
+            " left join `map` as `region.capital` on (`region`.`capital` = `region.capital`.`id`)",
+        ),
+    );
+
+    let mut columns = vec![
+        Column::with_table("region", "id"),
+        Column::with_table("region", "name"),
+        Column::with_table("region.parent", "id"),
+        Column::with_table("region.parent", "name"),
+        Column::with_table("region.parent", "parent"),
+    ];
+    for table in ["region.parent.capital", "region.capital"] {
+        for name in ["id", "name", "difficulty", "last_played"] {
+            columns.push(Column::with_table(table, name));
+        }
+    }
+    let mapping = <Region as FromQueryResult>::Mapping::from_columns(&columns);
+    let row = |grandparent| {
+        vec![
+            Value::UInt(2),
+            Value::Bytes(b"child".to_vec()),
+            Value::UInt(1),
+            Value::Bytes(b"parent".to_vec()),
+            grandparent,
+            Value::Null,
+            Value::Null,
+            Value::Null,
+            Value::Null,
+            Value::UInt(3),
+            Value::Bytes(b"capital".to_vec()),
+            Value::UTiny(4),
+            Value::Null,
+        ]
+    };
+    let region = Region::from_mapping_and_row(&mapping, &mut row(Value::Null)).unwrap();
+    let parent = region.parent.unwrap();
+    assert_eq!((parent.id, parent.name.as_str()), (1, "parent"));
+    assert!(parent.parent.is_none());
+    assert!(parent.capital.is_none());
+    let capital = region.capital.unwrap();
+    assert_eq!((capital.id, capital.difficulty), (3, 4));
+
+    // a grandparent beyond the depth can't be loaded, which isn't silently dropped
+    assert!(matches!(
+        Region::from_mapping_and_row(&mapping, &mut row(Value::UInt(0))),
+        Err(ParseError::MissingField("Region.parent"))
+    ));
+}
+
+#[test]
+fn test_has_many() {
+    assert_eq!(
+        User::build_query(),
+        "select `user`.`id`, `user`.`name` from `user`"
+    );
+    let addresses = User::addresses();
+    assert_eq!(addresses.foreign_key(), "user");
+    assert_eq!(
+        addresses.query(3).unwrap(),
+        "select `address`.`user`, `address`.`id`, `address`.`street` from `address` where `address`.`user` in (?, ?, ?)"
+    );
+
+    let columns = [
+        Column::with_table("user", "id"),
+        Column::with_table("user", "name"),
+    ];
+    let mapping = <User as FromQueryResult>::Mapping::from_columns(&columns);
+    let mut users = [1, 2]
+        .into_iter()
+        .map(|id| {
+            let mut row = vec![Value::UInt(id), Value::Bytes(b"user".to_vec())];
+            User::from_mapping_and_row(&mapping, &mut row).unwrap()
+        })
+        .collect::<Vec<_>>();
+    assert!(users[0].addresses.is_empty());
+
+    let address = |id: u32| Address {
+        id,
+        street: String::from("street"),
+    };
+    addresses.stitch(
+        &mut users,
+        vec![
+            (2, address(10)),
+            (1, address(11)),
+            (2, address(12)),
+            (3, address(13)),
+        ],
+    );
+    let ids = |user: &User| user.addresses.iter().map(|x| x.id).collect::<Vec<_>>();
+    assert_eq!(ids(&users[0]), [11]);
+    assert_eq!(ids(&users[1]), [10, 12]);
+}
+
+#[test]
+fn test_mysql_enum() {
+    assert_eq!(
+        Permission::NAMES,
+        ["read", "write", "admin", "change_owner"]
+    );
+    assert_eq!(
+        Value::from(Permission::ChangeOwner),
+        Value::Bytes(b"change_owner".to_vec())
+    );
+    assert_eq!(
+        Value::from(HashSet::from([Permission::Administrate, Permission::Read])),
+        Value::Bytes(b"read,admin".to_vec())
+    );
+    assert!(TryInto::<Permission>::try_into(Value::Bytes(b"Read".to_vec())).is_err());
+
+    let columns = [
+        Column::with_table("member", "id"),
+        Column::with_table("member", "role"),
+        Column::with_table("member", "fallback"),
+        Column::with_table("member", "permissions"),
+    ];
+    let mapping = <Member as FromQueryResult>::Mapping::from_columns(&columns);
+    let mut row = vec![
+        Value::UInt(1),
+        Value::Bytes(b"admin".to_vec()),
+        Value::Null,
+        Value::Bytes(b"read,change_owner".to_vec()),
+    ];
+    let member = Member::from_mapping_and_row(&mapping, &mut row).unwrap();
+    assert_eq!(member.role, Permission::Administrate);
+    assert_eq!(member.fallback, None);
+    assert_eq!(
+        member.permissions,
+        HashSet::from([Permission::Read, Permission::ChangeOwner])
+    );
+
+    let mut row = vec![
+        Value::UInt(1),
+        Value::Bytes(b"read".to_vec()),
+        Value::Bytes(b"write".to_vec()),
+        Value::Bytes(Vec::new()),
+    ];
+    let member = Member::from_mapping_and_row(&mapping, &mut row).unwrap();
+    assert_eq!(member.fallback, Some(Permission::Write));
+    assert!(member.permissions.is_empty());
+}
+
+#[test]
+fn test_field_attributes() {
+    assert_eq!(<Account as Model>::PRIMARY, "accountId");
+    assert_eq!(
+        Account::build_query(),
+        concat!(
+            "select `account`.`accountId`, `account`.`userName`, `account`.`active`, `account`.`nickname`, `account`.`score`, ",
+            "`account`.`pos_x`, `account`.`pos_y`, `account`.`pos_z`, ",
+            "`account.homeMap`.`id`, `account.homeMap`.`name`, `account.homeMap`.`difficulty`, `account.homeMap`.`last_played` ",
+            "from `account` left join `map` as `account.homeMap` on (`account`.`homeMap` = `account.homeMap`.`id`)",
+        ),
+    );
+    let (query, params) = Account::select()
+        .filter(Account::user_name().eq("foo"))
+        .filter(Account::active().eq(Value::Bytes(b"Y".to_vec())))
+        .build()
+        .unwrap();
+    assert_eq!(
+        query,
+        Account::build_query() + " where (`account`.`userName` = ?) and (`account`.`active` = ?)"
+    );
+    assert_eq!(params.len(), 2);
+
+    let mut columns = vec![
+        Column::with_table("account", "accountId"),
+        Column::with_table("account", "userName"),
+        Column::with_table("account", "active"),
+        Column::with_table("account", "pos_x"),
+        Column::with_table("account", "pos_y"),
+        Column::with_table("account", "pos_z"),
+    ];
+    for name in ["id", "name", "difficulty", "last_played"] {
+        columns.push(Column::with_table("account.homeMap", name));
+    }
+    let mapping = <Account as FromQueryResult>::Mapping::from_columns(&columns);
+    let mut row = vec![
+        Value::UInt(1),
+        Value::Bytes(b"foo".to_vec()),
+        Value::Bytes(b"Y".to_vec()),
+        Value::Float(1.0),
+        Value::Float(2.0),
+        Value::Float(3.0),
+        Value::UInt(2),
+        Value::Bytes(b"map".to_vec()),
+        Value::UTiny(1),
+        Value::Null,
+    ];
+    let account = Account::from_mapping_and_row(&mapping, &mut row).unwrap();
+    assert_eq!((account.id, account.user_name.as_str()), (1, "foo"));
+    assert!(account.active);
+    assert_eq!(account.nickname, None);
+    assert_eq!(account.score, 100);
+    assert!(account.cached.is_empty());
+    assert_eq!(account.position.z, 3.0);
+    assert_eq!(account.map.unwrap().id, 2);
+
+    let mut row = vec![Value::UInt(1), Value::Null, Value::Bytes(b"?".to_vec())];
+    assert!(Account::from_mapping_and_row(&mapping, &mut row).is_err());
+}
+
+#[test]
+fn test_plain_rows() {
+    let columns = [
+        Column::with_table("", "count(*)"),
+        Column::with_table("", "max_id"),
+    ];
+    let row = || vec![Value::ULong(3), Value::Null];
+
+    // the mapping only counts the columns, so it's shared by all plain rows
+    fn mapping_of<T: FromQueryResult>(columns: &[Column]) -> T::Mapping {
+        <T::Mapping as FromQueryResultMapping<T>>::from_columns(columns)
+    }
+
+    let mapping = mapping_of::<(u64, Option<u32>)>(&columns);
+    let tuple = <(u64, Option<u32>)>::from_mapping_and_row(&mapping, &mut row()).unwrap();
+    assert_eq!(tuple, (3, None));
+    assert!(matches!(
+        <(u64, Option<u32>)>::from_mapping_and_row(&mapping, &mut vec![Value::ULong(3)]),
+        Err(ParseError::RowLengthMismatch)
+    ));
+
+    let mapping = mapping_of::<u64>(&columns[..1]);
+    assert_eq!(
+        u64::from_mapping_and_row(&mapping, &mut vec![Value::ULong(3)]).unwrap(),
+        3
+    );
+    let mapping = mapping_of::<u64>(&columns);
+    assert!(matches!(
+        u64::from_mapping_and_row(&mapping, &mut row()),
+        Err(ParseError::RowLengthMismatch)
+    ));
+    // enums are scalars as well
+    let mapping = mapping_of::<Permission>(&columns[..1]);
+    assert_eq!(
+        Permission::from_mapping_and_row(&mapping, &mut vec![Value::Bytes(b"admin".to_vec())])
+            .unwrap(),
+        Permission::Administrate
+    );
+    assert_eq!(
+        Option::<Permission>::from_mapping_and_row(&mapping, &mut vec![Value::Null]).unwrap(),
+        None
+    );
+    let mapping = mapping_of::<HashSet<Permission>>(&columns[..1]);
+    assert_eq!(
+        HashSet::<Permission>::from_mapping_and_row(
+            &mapping,
+            &mut vec![Value::Bytes(b"read".to_vec())]
+        )
+        .unwrap(),
+        HashSet::from([Permission::Read])
+    );
+
+    let mapping = <HashMap<String, Value> as FromQueryResult>::Mapping::from_columns(&columns);
+    let map = HashMap::<String, Value>::from_mapping_and_row(&mapping, &mut row()).unwrap();
+    assert_eq!(
+        map,
+        HashMap::from([
+            (String::from("count(*)"), Value::ULong(3)),
+            (String::from("max_id"), Value::Null),
+        ])
+    );
+}
+
+#[cfg(feature = "uuid")]
+#[test]
+fn test_uuid_primary() {
+    use {mysql_connector::types::SwappedUuid, uuid::Uuid};
+
+    let id = Uuid::from_u128(0x6ccd780c_baba_1026_9564_5b8c656024db);
+    let columns = ["id", "token", "revoked"].map(|name| Column::with_table("session", name));
+    let mapping = <Session as FromQueryResult>::Mapping::from_columns(&columns);
+    let mut row = vec![
+        Value::from(SwappedUuid(id)),
+        Value::Bytes(id.hyphenated().to_string().into_bytes()),
+        Value::Null,
+    ];
+    let session = Session::from_mapping_and_row(&mapping, &mut row).unwrap();
+    assert_eq!(
+        (*session.primary(), session.token, session.revoked),
+        (id, id, None)
+    );
+    assert_eq!(
+        Into::<Value>::into(session.primary()),
+        Value::Bytes(vec![
+            16, 38, 186, 186, 108, 205, 120, 12, 149, 100, 91, 140, 101, 96, 36, 219
+        ])
+    );
+}
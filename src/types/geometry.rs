@@ -0,0 +1,362 @@
+use {
+    super::{Value, ValueType},
+    crate::{connection::ParseBuf, error::ParseError, model::impl_from_query_result},
+    geo_types::{
+        Coord, Geometry, GeometryCollection, LineString, MultiLineString, MultiPoint, MultiPolygon,
+        Point, Polygon,
+    },
+};
+
+const POINT: u32 = 1;
+const LINE_STRING: u32 = 2;
+const POLYGON: u32 = 3;
+const MULTI_POINT: u32 = 4;
+const MULTI_LINE_STRING: u32 = 5;
+const MULTI_POLYGON: u32 = 6;
+const GEOMETRY_COLLECTION: u32 = 7;
+
+/// Maximum nesting of geometry collections, which limits the recursion for invalid values.
+const MAX_COLLECTION_DEPTH: usize = 32;
+
+/// Spatial value with its spatial reference system identifier.
+///
+/// MySQL stores geometries as a 4-byte little-endian SRID followed by the
+/// [WKB](https://dev.mysql.com/doc/refman/8.4/en/gis-data-formats.html#gis-wkb-format) representation.
+/// `T` can be [`Geometry`] or one of the types it contains, like [`Point`] or [`Polygon`];
+/// parsing fails if the column contains a different type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spatial<T = Geometry<f64>> {
+    pub srid: u32,
+    pub geometry: T,
+}
+
+impl<T> Spatial<T> {
+    pub fn new(srid: u32, geometry: T) -> Self {
+        Self { srid, geometry }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.geometry
+    }
+}
+
+impl<T: Into<Geometry<f64>>> From<Spatial<T>> for Value {
+    fn from(value: Spatial<T>) -> Self {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(&value.srid.to_le_bytes());
+        write_geometry(&mut buf, &value.geometry.into());
+        Value::Bytes(buf)
+    }
+}
+
+impl<T> TryInto<Spatial<T>> for Value
+where
+    Geometry<f64>: TryInto<T>,
+{
+    type Error = ParseError;
+
+    fn try_into(self) -> Result<Spatial<T>, Self::Error> {
+        match self {
+            Value::Bytes(x) => {
+                let spatial = parse(&x).and_then(|(srid, geometry)| {
+                    Some(Spatial::new(srid, geometry.try_into().ok()?))
+                });
+                spatial.ok_or(ParseError::InvalidValue(ValueType::Geometry, x))
+            }
+            _ => Err(Self::Error::wrong_value(ValueType::Geometry, self)),
+        }
+    }
+}
+
+impl<T> TryInto<Option<Spatial<T>>> for Value
+where
+    Geometry<f64>: TryInto<T>,
+{
+    type Error = ParseError;
+
+    fn try_into(self) -> Result<Option<Spatial<T>>, Self::Error> {
+        match self {
+            Value::Null => Ok(None),
+            x => x.try_into().map(Some),
+        }
+    }
+}
+
+impl_from_query_result!(impl<T> Spatial<T>);
+
+fn parse(bytes: &[u8]) -> Option<(u32, Geometry<f64>)> {
+    let mut buf = ParseBuf(bytes);
+    let srid = u32::from_le_bytes(buf.checked_eat(4).ok()?.try_into().ok()?);
+    let mut reader = WkbReader {
+        buf,
+        le: true,
+        depth: 0,
+    };
+    let geometry = reader.geometry()?;
+    match reader.buf.is_empty() {
+        true => Some((srid, geometry)),
+        false => None,
+    }
+}
+
+struct WkbReader<'a> {
+    buf: ParseBuf<'a>,
+    le: bool,
+    /// Number of geometry collections containing the current geometry.
+    depth: usize,
+}
+
+impl WkbReader<'_> {
+    fn u32(&mut self) -> Option<u32> {
+        let bytes = self.buf.checked_eat(4).ok()?.try_into().ok()?;
+        Some(match self.le {
+            true => u32::from_le_bytes(bytes),
+            false => u32::from_be_bytes(bytes),
+        })
+    }
+
+    fn f64(&mut self) -> Option<f64> {
+        let bytes = self.buf.checked_eat(8).ok()?.try_into().ok()?;
+        Some(match self.le {
+            true => f64::from_le_bytes(bytes),
+            false => f64::from_be_bytes(bytes),
+        })
+    }
+
+    /// Reads the byte order and the type of the next geometry.
+    fn header(&mut self) -> Option<u32> {
+        self.le = match self.buf.checked_eat(1).ok()?[0] {
+            0 => false,
+            1 => true,
+            _ => return None,
+        };
+        self.u32()
+    }
+
+    fn list<T>(&mut self, mut f: impl FnMut(&mut Self) -> Option<T>) -> Option<Vec<T>> {
+        let len = self.u32()? as usize;
+        // every element needs at least one byte, so this limits allocations for invalid lengths
+        let mut list = Vec::with_capacity(len.min(self.buf.len()));
+        for _ in 0..len {
+            list.push(f(self)?);
+        }
+        Some(list)
+    }
+
+    fn nested<T>(&mut self, ty: u32, f: impl FnOnce(&mut Self) -> Option<T>) -> Option<T> {
+        match self.header()? == ty {
+            true => f(self),
+            false => None,
+        }
+    }
+
+    fn coord(&mut self) -> Option<Coord<f64>> {
+        Some(Coord {
+            x: self.f64()?,
+            y: self.f64()?,
+        })
+    }
+
+    fn line_string(&mut self) -> Option<LineString<f64>> {
+        self.list(Self::coord).map(LineString)
+    }
+
+    fn polygon(&mut self) -> Option<Polygon<f64>> {
+        let mut rings = self.list(Self::line_string)?.into_iter();
+        let exterior = rings.next().unwrap_or_else(|| LineString(Vec::new()));
+        Some(Polygon::new(exterior, rings.collect()))
+    }
+
+    fn geometry(&mut self) -> Option<Geometry<f64>> {
+        Some(match self.header()? {
+            POINT => Geometry::Point(Point(self.coord()?)),
+            LINE_STRING => Geometry::LineString(self.line_string()?),
+            POLYGON => Geometry::Polygon(self.polygon()?),
+            MULTI_POINT => Geometry::MultiPoint(MultiPoint(
+                self.list(|x| x.nested(POINT, |x| x.coord().map(Point)))?,
+            )),
+            MULTI_LINE_STRING => Geometry::MultiLineString(MultiLineString(
+                self.list(|x| x.nested(LINE_STRING, Self::line_string))?,
+            )),
+            MULTI_POLYGON => Geometry::MultiPolygon(MultiPolygon(
+                self.list(|x| x.nested(POLYGON, Self::polygon))?,
+            )),
+            GEOMETRY_COLLECTION => {
+                if self.depth == MAX_COLLECTION_DEPTH {
+                    return None;
+                }
+                self.depth += 1;
+                let geometries = self.list(Self::geometry)?;
+                self.depth -= 1;
+                Geometry::GeometryCollection(GeometryCollection(geometries))
+            }
+            _ => return None,
+        })
+    }
+}
+
+fn write_header(buf: &mut Vec<u8>, ty: u32) {
+    buf.push(1);
+    buf.extend_from_slice(&ty.to_le_bytes());
+}
+
+fn write_len(buf: &mut Vec<u8>, len: usize) {
+    buf.extend_from_slice(&(len as u32).to_le_bytes());
+}
+
+fn write_coord(buf: &mut Vec<u8>, coord: &Coord<f64>) {
+    buf.extend_from_slice(&coord.x.to_le_bytes());
+    buf.extend_from_slice(&coord.y.to_le_bytes());
+}
+
+fn write_line_string(buf: &mut Vec<u8>, line_string: &LineString<f64>) {
+    write_len(buf, line_string.0.len());
+    for coord in &line_string.0 {
+        write_coord(buf, coord);
+    }
+}
+
+fn write_polygon(buf: &mut Vec<u8>, polygon: &Polygon<f64>) {
+    write_len(buf, polygon.interiors().len() + 1);
+    write_line_string(buf, polygon.exterior());
+    for interior in polygon.interiors() {
+        write_line_string(buf, interior);
+    }
+}
+
+fn write_geometry(buf: &mut Vec<u8>, geometry: &Geometry<f64>) {
+    match geometry {
+        Geometry::Point(point) => {
+            write_header(buf, POINT);
+            write_coord(buf, &point.0);
+        }
+        Geometry::Line(line) => {
+            write_header(buf, LINE_STRING);
+            write_line_string(buf, &LineString::from(*line));
+        }
+        Geometry::LineString(line_string) => {
+            write_header(buf, LINE_STRING);
+            write_line_string(buf, line_string);
+        }
+        Geometry::Polygon(polygon) => {
+            write_header(buf, POLYGON);
+            write_polygon(buf, polygon);
+        }
+        Geometry::MultiPoint(points) => {
+            write_header(buf, MULTI_POINT);
+            write_len(buf, points.0.len());
+            for point in points {
+                write_header(buf, POINT);
+                write_coord(buf, &point.0);
+            }
+        }
+        Geometry::MultiLineString(line_strings) => {
+            write_header(buf, MULTI_LINE_STRING);
+            write_len(buf, line_strings.0.len());
+            for line_string in line_strings {
+                write_header(buf, LINE_STRING);
+                write_line_string(buf, line_string);
+            }
+        }
+        Geometry::MultiPolygon(polygons) => {
+            write_header(buf, MULTI_POLYGON);
+            write_len(buf, polygons.0.len());
+            for polygon in polygons {
+                write_header(buf, POLYGON);
+                write_polygon(buf, polygon);
+            }
+        }
+        Geometry::GeometryCollection(geometries) => {
+            write_header(buf, GEOMETRY_COLLECTION);
+            write_len(buf, geometries.0.len());
+            for geometry in geometries {
+                write_geometry(buf, geometry);
+            }
+        }
+        Geometry::Rect(rect) => {
+            write_header(buf, POLYGON);
+            write_polygon(buf, &rect.to_polygon());
+        }
+        Geometry::Triangle(triangle) => {
+            write_header(buf, POLYGON);
+            write_polygon(buf, &triangle.to_polygon());
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::Spatial,
+        crate::types::Value,
+        geo_types::{
+            line_string, point, polygon, Geometry, GeometryCollection, LineString, Point, Polygon,
+        },
+    };
+
+    #[test]
+    fn conversion() {
+        // SELECT HEX(ST_GeomFromText('POINT(1 2)', 4326))
+        let value = Value::Bytes(
+            hex::decode("E61000000101000000000000000000F03F0000000000000040").unwrap(),
+        );
+        let spatial: Spatial<Point> = value.clone().try_into().unwrap();
+        assert_eq!(spatial, Spatial::new(4326, point!(x: 1.0, y: 2.0)));
+        assert_eq!(Value::from(spatial), value);
+        let spatial: Spatial = value.clone().try_into().unwrap();
+        assert_eq!(spatial.geometry, Geometry::Point(point!(x: 1.0, y: 2.0)));
+        assert!(TryInto::<Spatial<Polygon>>::try_into(value).is_err());
+
+        let collection = Spatial::new(
+            0,
+            Geometry::GeometryCollection(GeometryCollection(vec![
+                Geometry::LineString(line_string![(x: 0.0, y: 0.0), (x: 1.0, y: 1.0)]),
+                Geometry::Polygon(polygon![
+                    exterior: [(x: 0.0, y: 0.0), (x: 4.0, y: 0.0), (x: 0.0, y: 4.0)],
+                    interiors: [[(x: 1.0, y: 1.0), (x: 2.0, y: 1.0), (x: 1.0, y: 2.0)]],
+                ]),
+                Geometry::MultiPoint(vec![point!(x: 3.0, y: 3.0)].into()),
+            ])),
+        );
+        let value = Value::from(collection.clone());
+        assert_eq!(TryInto::<Spatial>::try_into(value).unwrap(), collection);
+
+        let parsed: Option<Spatial> = Value::Null.try_into().unwrap();
+        assert_eq!(parsed, None);
+        assert!(TryInto::<Spatial>::try_into(Value::Bytes(vec![0; 9])).is_err());
+        assert!(TryInto::<Spatial>::try_into(Value::Int(1)).is_err());
+    }
+
+    #[test]
+    fn empty_polygon() {
+        // written with a single empty ring
+        let empty = Spatial::new(0, Polygon::new(LineString(Vec::new()), Vec::new()));
+        let value = Value::from(empty.clone());
+        assert_eq!(
+            value,
+            Value::Bytes(hex::decode("0000000001030000000100000000000000").unwrap())
+        );
+        assert_eq!(TryInto::<Spatial<Polygon>>::try_into(value).unwrap(), empty);
+    }
+
+    #[test]
+    fn invalid() {
+        let mut bytes: Vec<u8> = Value::from(Spatial::new(0, point!(x: 1.0, y: 2.0)))
+            .try_into()
+            .unwrap();
+        bytes.push(0);
+        assert!(TryInto::<Spatial>::try_into(Value::Bytes(bytes)).is_err());
+
+        // `depth` collections, each containing the next one
+        let nested = |depth: usize| {
+            let mut bytes = vec![0; 4];
+            for _ in 1..depth {
+                bytes.extend_from_slice(&hex::decode("010700000001000000").unwrap());
+            }
+            bytes.extend_from_slice(&hex::decode("010700000000000000").unwrap());
+            Value::Bytes(bytes)
+        };
+        assert!(TryInto::<Spatial>::try_into(nested(super::MAX_COLLECTION_DEPTH)).is_ok());
+        assert!(TryInto::<Spatial>::try_into(nested(super::MAX_COLLECTION_DEPTH + 1)).is_err());
+    }
+}
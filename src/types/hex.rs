@@ -1,6 +1,6 @@
 use {
     super::{value::impl_try_into_option, Value, ValueType},
-    crate::error::ParseError,
+    crate::{error::ParseError, model::impl_from_query_result},
     std::{fmt, ops},
 };
 
@@ -104,6 +104,7 @@ impl TryInto<Hex> for Value {
 }
 
 impl_try_into_option!(Hex);
+impl_from_query_result!(Hex);
 
 #[cfg(test)]
 mod test {
@@ -116,6 +117,6 @@ mod test {
         let hex = Hex::new("hello hex".as_bytes().to_vec());
         assert_eq!(hex.to_string().as_str(), "0x68656c6c6f20686578");
         assert_eq!(Hex::from_str(&hex.to_string()).unwrap(), hex);
-        assert_eq!(Hex::try_from_hex(&hex.to_string()).unwrap(), hex);
+        assert_eq!(Hex::try_from_hex(hex.to_string()).unwrap(), hex);
     }
 }
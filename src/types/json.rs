@@ -0,0 +1,118 @@
+use {
+    super::{Value, ValueType},
+    crate::{
+        error::{ParseError, SerializeError},
+        model::impl_from_query_result,
+    },
+    serde::{de::DeserializeOwned, Serialize},
+    std::ops,
+};
+
+/// `JSON` document, which is (de)serialized with `serde_json`.
+///
+/// It is sent as [`Value::String`], which the server parses when it's stored in a `JSON` column or
+/// passed to a function like `json_extract(?, ...)`. `JSON` columns of MariaDB are `LONGTEXT` columns,
+/// which are deserialized as well.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Json<T>(pub T);
+
+impl<T> Json<T> {
+    pub fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> ops::Deref for Json<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> ops::DerefMut for Json<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: Serialize> TryFrom<Json<T>> for Value {
+    type Error = SerializeError;
+
+    fn try_from(value: Json<T>) -> Result<Self, Self::Error> {
+        serde_json::to_string(&value.0)
+            .map(Value::String)
+            .map_err(|err| SerializeError::InvalidValue(ValueType::Json, Box::new(err)))
+    }
+}
+
+impl<T: DeserializeOwned> TryInto<Json<T>> for Value {
+    type Error = ParseError;
+
+    fn try_into(self) -> Result<Json<T>, Self::Error> {
+        match self {
+            Value::Bytes(x) => serde_json::from_slice(&x).map(Json).map_err(Into::into),
+            Value::String(x) => serde_json::from_str(&x).map(Json).map_err(Into::into),
+            _ => Err(Self::Error::wrong_value(ValueType::Json, self)),
+        }
+    }
+}
+
+impl<T: DeserializeOwned> TryInto<Option<Json<T>>> for Value {
+    type Error = ParseError;
+
+    fn try_into(self) -> Result<Option<Json<T>>, Self::Error> {
+        match self {
+            Value::Null => Ok(None),
+            x => x.try_into().map(Some),
+        }
+    }
+}
+
+impl_from_query_result!(impl<T> Json<T>);
+
+#[cfg(test)]
+mod test {
+    use {
+        super::Json,
+        crate::{
+            bitflags::ColumnFlags, connection::types::ColumnType, types::Value, ParseBuf, Serialize,
+        },
+        std::collections::BTreeMap,
+    };
+
+    #[test]
+    fn conversion() {
+        let json = Json(BTreeMap::from([("a", vec![1, 2]), ("b\"", vec![])]));
+        let value = Value::try_from(json.clone()).unwrap();
+        assert_eq!(value, Value::String(r#"{"a":[1,2],"b\"":[]}"#.to_owned()));
+        assert_eq!(value.column_type(), ColumnType::VarString);
+        // quoted string literal in text queries
+        assert_eq!(value.to_string(), r#"'{\"a\":[1,2],\"b\\\"\":[]}'"#);
+        assert_eq!(
+            TryInto::<Json<BTreeMap<String, Vec<u8>>>>::try_into(value.clone()).unwrap(),
+            Json(BTreeMap::from([
+                ("a".to_owned(), vec![1, 2]),
+                ("b\"".to_owned(), vec![])
+            ]))
+        );
+
+        let mut buf = Vec::new();
+        value.serialize(&mut buf);
+        let value =
+            Value::deserialize_bin(ColumnType::Json, ColumnFlags::empty(), &mut ParseBuf(&buf));
+        let parsed: Json<BTreeMap<String, Vec<u8>>> = value.unwrap().try_into().unwrap();
+        assert_eq!(parsed["a"], [1, 2]);
+
+        let parsed: Option<Json<Vec<u8>>> = Value::Bytes(b"[3]".to_vec()).try_into().unwrap();
+        assert_eq!(parsed, Some(Json(vec![3])));
+        let parsed: Option<Json<Vec<u8>>> = Value::Null.try_into().unwrap();
+        assert_eq!(parsed, None);
+        assert!(TryInto::<Json<u8>>::try_into(Value::Bytes(b"[".to_vec())).is_err());
+        assert!(TryInto::<Json<u8>>::try_into(Value::Int(1)).is_err());
+    }
+}
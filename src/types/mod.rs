@@ -1,8 +1,28 @@
+#[cfg(feature = "geo")]
+mod geometry;
 mod hex;
+#[cfg(feature = "json")]
+mod json;
+mod mysql_enum;
+#[cfg(feature = "uuid")]
+mod uuid;
 mod value;
 
 pub use {
     crate::connection::types::{auth_plugin::AuthPlugin, column::Column},
     hex::Hex,
+    mysql_enum::MysqlEnum,
     value::*,
 };
+
+#[cfg(feature = "geo")]
+#[cfg_attr(doc, doc(cfg(feature = "geo")))]
+pub use geometry::Spatial;
+
+#[cfg(feature = "json")]
+#[cfg_attr(doc, doc(cfg(feature = "json")))]
+pub use json::Json;
+
+#[cfg(feature = "uuid")]
+#[cfg_attr(doc, doc(cfg(feature = "uuid")))]
+pub use uuid::SwappedUuid;
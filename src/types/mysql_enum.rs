@@ -0,0 +1,99 @@
+use {
+    super::{Value, ValueType},
+    crate::{
+        error::ParseError,
+        model::{impl_from_query_result, EmptyMapping, FromQueryResult},
+    },
+    std::{collections::HashSet, hash::Hash},
+};
+
+/// Rust enum mapped to a MySQL `ENUM` column, which is transferred as string.
+///
+/// This is implemented by `#[derive(MysqlEnum)]`, which also implements the conversions from and to [`Value`]
+/// and [`FromQueryResult`] to read rows with a single column.
+/// The name of a variant can be changed with `#[mysql_connector(rename = "...")]`,
+/// the names of all variants with `#[mysql_connector(rename_all = "...")]` on the enum
+/// (`lowercase`, `UPPERCASE`, `snake_case`, `SCREAMING_SNAKE_CASE` or `kebab-case`).
+///
+/// A `HashSet` of the enum is mapped to a `SET` column.
+/// Fields of models need `#[mysql_connector(value)]`, so they aren't treated as references.
+pub trait MysqlEnum: Sized {
+    /// Names of all variants, in the order of the column definition.
+    const NAMES: &'static [&'static str];
+
+    fn name(&self) -> &'static str;
+
+    fn from_name(name: &str) -> Option<Self>;
+
+    fn to_value(&self) -> Value {
+        Value::Bytes(self.name().as_bytes().to_vec())
+    }
+
+    fn from_value(value: Value) -> Result<Self, ParseError> {
+        match value {
+            Value::Bytes(bytes) => std::str::from_utf8(&bytes)
+                .ok()
+                .and_then(Self::from_name)
+                .ok_or(ParseError::InvalidValue(ValueType::Enum, bytes)),
+            _ => Err(ParseError::wrong_value(ValueType::Enum, value)),
+        }
+    }
+}
+
+impl<E: MysqlEnum> From<HashSet<E>> for Value {
+    fn from(value: HashSet<E>) -> Self {
+        let mut names = value.iter().map(E::name).collect::<Vec<_>>();
+        names.sort_by_key(|name| E::NAMES.iter().position(|x| x == name));
+        Value::Bytes(names.join(",").into_bytes())
+    }
+}
+
+impl<E: MysqlEnum + Eq + Hash> TryInto<HashSet<E>> for Value {
+    type Error = ParseError;
+
+    fn try_into(self) -> Result<HashSet<E>, Self::Error> {
+        match self {
+            Value::Bytes(bytes) => {
+                let set = std::str::from_utf8(&bytes).ok().and_then(|x| {
+                    x.split(',')
+                        .filter(|x| !x.is_empty())
+                        .map(E::from_name)
+                        .collect::<Option<HashSet<E>>>()
+                });
+                set.ok_or(ParseError::InvalidValue(ValueType::Set, bytes))
+            }
+            _ => Err(ParseError::wrong_value(ValueType::Set, self)),
+        }
+    }
+}
+
+impl<E: MysqlEnum + Eq + Hash> TryInto<Option<HashSet<E>>> for Value {
+    type Error = ParseError;
+
+    fn try_into(self) -> Result<Option<HashSet<E>>, Self::Error> {
+        match self {
+            Value::Null => Ok(None),
+            x => x.try_into().map(Some),
+        }
+    }
+}
+
+/// The enum itself is implemented by `#[derive(MysqlEnum)]`.
+impl<E: MysqlEnum> FromQueryResult for Option<E> {
+    type Mapping = EmptyMapping;
+
+    fn from_mapping_and_row(
+        mapping: &Self::Mapping,
+        row: &mut Vec<Value>,
+    ) -> Result<Self, ParseError> {
+        if mapping.len() != 1 || row.len() != 1 {
+            return Err(ParseError::RowLengthMismatch);
+        }
+        match row[0].take() {
+            Value::Null => Ok(None),
+            x => E::from_value(x).map(Some),
+        }
+    }
+}
+
+impl_from_query_result!(impl<E> HashSet<E>);
@@ -0,0 +1,173 @@
+use {
+    super::{Value, ValueType},
+    crate::{error::ParseError, model::impl_from_query_result},
+    ::uuid::Uuid,
+    std::ops,
+};
+
+impl From<Uuid> for Value {
+    fn from(value: Uuid) -> Self {
+        Value::Bytes(value.as_bytes().to_vec())
+    }
+}
+
+/// Accepts `BINARY(16)` and the 36 character text representation (`CHAR(36)`, `UUID()`).
+impl TryInto<Uuid> for Value {
+    type Error = ParseError;
+
+    fn try_into(self) -> Result<Uuid, Self::Error> {
+        match self {
+            Value::Bytes(x) => parse(x),
+            _ => Err(Self::Error::wrong_value(ValueType::Uuid, self)),
+        }
+    }
+}
+
+impl TryInto<Option<Uuid>> for Value {
+    type Error = ParseError;
+
+    fn try_into(self) -> Result<Option<Uuid>, Self::Error> {
+        match self {
+            Value::Null => Ok(None),
+            x => x.try_into().map(Some),
+        }
+    }
+}
+
+fn parse(bytes: Vec<u8>) -> Result<Uuid, ParseError> {
+    let uuid = match bytes.len() {
+        16 => Uuid::from_slice(&bytes).ok(),
+        36 => Uuid::try_parse_ascii(&bytes).ok(),
+        _ => None,
+    };
+    uuid.ok_or(ParseError::InvalidValue(ValueType::Uuid, bytes))
+}
+
+/// [`Uuid`] stored in the layout of `UUID_TO_BIN(uuid, 1)`.
+///
+/// The time-low and time-high parts are swapped, so time-based (version 1) UUIDs are stored in
+/// ascending order, which keeps indexes compact. Text values are not swapped.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SwappedUuid(pub Uuid);
+
+impl SwappedUuid {
+    pub fn new(value: Uuid) -> Self {
+        Self(value)
+    }
+
+    pub fn into_inner(self) -> Uuid {
+        self.0
+    }
+
+    fn swap(bytes: &[u8; 16]) -> [u8; 16] {
+        let mut swapped = [0; 16];
+        swapped[0..2].copy_from_slice(&bytes[6..8]);
+        swapped[2..4].copy_from_slice(&bytes[4..6]);
+        swapped[4..8].copy_from_slice(&bytes[0..4]);
+        swapped[8..].copy_from_slice(&bytes[8..]);
+        swapped
+    }
+
+    fn unswap(bytes: &[u8; 16]) -> [u8; 16] {
+        let mut unswapped = [0; 16];
+        unswapped[0..4].copy_from_slice(&bytes[4..8]);
+        unswapped[4..6].copy_from_slice(&bytes[2..4]);
+        unswapped[6..8].copy_from_slice(&bytes[0..2]);
+        unswapped[8..].copy_from_slice(&bytes[8..]);
+        unswapped
+    }
+}
+
+impl From<Uuid> for SwappedUuid {
+    fn from(value: Uuid) -> Self {
+        Self(value)
+    }
+}
+
+impl From<SwappedUuid> for Uuid {
+    fn from(value: SwappedUuid) -> Self {
+        value.0
+    }
+}
+
+impl ops::Deref for SwappedUuid {
+    type Target = Uuid;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<SwappedUuid> for Value {
+    fn from(value: SwappedUuid) -> Self {
+        Value::Bytes(SwappedUuid::swap(value.0.as_bytes()).to_vec())
+    }
+}
+
+impl TryInto<SwappedUuid> for Value {
+    type Error = ParseError;
+
+    fn try_into(self) -> Result<SwappedUuid, Self::Error> {
+        match self {
+            Value::Bytes(x) => match <[u8; 16]>::try_from(x.as_slice()) {
+                Ok(bytes) => Ok(SwappedUuid(Uuid::from_bytes(SwappedUuid::unswap(&bytes)))),
+                Err(_) => parse(x).map(SwappedUuid),
+            },
+            _ => Err(Self::Error::wrong_value(ValueType::Uuid, self)),
+        }
+    }
+}
+
+impl TryInto<Option<SwappedUuid>> for Value {
+    type Error = ParseError;
+
+    fn try_into(self) -> Result<Option<SwappedUuid>, Self::Error> {
+        match self {
+            Value::Null => Ok(None),
+            x => x.try_into().map(Some),
+        }
+    }
+}
+
+impl_from_query_result!(Uuid, SwappedUuid);
+
+#[cfg(test)]
+mod test {
+    use {
+        super::SwappedUuid,
+        crate::types::Value,
+        ::uuid::{uuid, Uuid},
+    };
+
+    const UUID: Uuid = uuid!("6ccd780c-baba-1026-9564-5b8c656024db");
+
+    #[test]
+    fn conversion() {
+        let bytes = UUID.as_bytes().to_vec();
+        assert_eq!(Value::from(UUID), Value::Bytes(bytes.clone()));
+        assert_eq!(
+            TryInto::<Uuid>::try_into(Value::Bytes(bytes)).unwrap(),
+            UUID
+        );
+        let text = Value::Bytes(UUID.hyphenated().to_string().into_bytes());
+        assert_eq!(TryInto::<Uuid>::try_into(text.clone()).unwrap(), UUID);
+        assert_eq!(
+            TryInto::<Option<Uuid>>::try_into(Value::Null).unwrap(),
+            None
+        );
+        assert!(TryInto::<Uuid>::try_into(Value::Bytes(vec![0; 15])).is_err());
+        assert!(TryInto::<Uuid>::try_into(Value::Int(1)).is_err());
+
+        // SELECT HEX(UUID_TO_BIN('6ccd780c-baba-1026-9564-5b8c656024db', 1))
+        let swapped = Value::Bytes(hex::decode("1026BABA6CCD780C95645B8C656024DB").unwrap());
+        assert_eq!(Value::from(SwappedUuid(UUID)), swapped);
+        assert_eq!(
+            TryInto::<SwappedUuid>::try_into(swapped).unwrap(),
+            SwappedUuid(UUID)
+        );
+        assert_eq!(
+            TryInto::<SwappedUuid>::try_into(text).unwrap(),
+            SwappedUuid(UUID)
+        );
+    }
+}
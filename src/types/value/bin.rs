@@ -25,6 +25,7 @@ impl Value {
             Self::Float(_) => 4,
             Self::Double(_) => 8,
             Self::Bytes(x) => lenenc_slice_len(x),
+            Self::String(x) => lenenc_slice_len(x.as_bytes()),
             Self::Date(0, 0, 0) | Self::Datetime(0, 0, 0, 0, 0, 0, 0) => 1,
             Self::Date(_, _, _) | Self::Datetime(_, _, _, 0, 0, 0, 0) => 5,
             Self::Datetime(_, _, _, _, _, _, 0) => 8,
@@ -32,6 +33,8 @@ impl Value {
             Self::Time(_, 0, 0, 0, 0, 0) => 1,
             Self::Time(_, _, _, _, _, 0) => 9,
             Self::Time(_, _, _, _, _, _) => 13,
+            #[cfg(feature = "decimal")]
+            Self::Decimal(x) => lenenc_slice_len(x.to_string().as_bytes()),
         }
     }
 }
@@ -51,6 +54,7 @@ impl Serialize for Value {
             Self::Float(x) => buf.put_f32_le(*x),
             Self::Double(x) => buf.put_f64_le(*x),
             Self::Bytes(x) => buf.put_lenenc_slice(x),
+            Self::String(x) => buf.put_lenenc_slice(x.as_bytes()),
             Self::Datetime(0, 0, 0, 0, 0, 0, 0) => {
                 buf.put_u8(0);
             }
@@ -99,6 +103,8 @@ impl Serialize for Value {
                 buf.put_u8(*secs);
                 buf.put_u32_le(*usecs);
             }
+            #[cfg(feature = "decimal")]
+            Self::Decimal(x) => buf.put_lenenc_slice(x.to_string().as_bytes()),
         }
     }
 }
@@ -238,13 +244,19 @@ impl Value {
             | ColumnType::LongBlob
             | ColumnType::Set
             | ColumnType::Enum
-            | ColumnType::Decimal
             | ColumnType::Bit
-            | ColumnType::NewDecimal
             | ColumnType::Geometry
             | ColumnType::Json => buf
                 .checked_eat_lenenc_slice()
                 .map(|x| Self::Bytes(x.to_vec())),
+            #[cfg(not(feature = "decimal"))]
+            ColumnType::Decimal | ColumnType::NewDecimal => buf
+                .checked_eat_lenenc_slice()
+                .map(|x| Self::Bytes(x.to_vec())),
+            #[cfg(feature = "decimal")]
+            ColumnType::Decimal | ColumnType::NewDecimal => {
+                Ok(Self::deserialize_decimal(buf.checked_eat_lenenc_slice()?))
+            }
             ColumnType::Date => Self::deserialize_date(buf),
             ColumnType::Timestamp | ColumnType::Datetime => Self::deserialize_datetime(buf),
             ColumnType::Time => Self::deserialize_time(buf),
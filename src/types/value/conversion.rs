@@ -1,7 +1,10 @@
 use {
     super::{Value, ValueType},
     crate::error::{ParseError, SerializeError},
-    chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike},
+    chrono::{
+        DateTime, Datelike, Duration, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone,
+        Timelike, Utc,
+    },
 };
 
 macro_rules! impl_conversion {
@@ -74,6 +77,9 @@ impl TryInto<String> for Value {
     fn try_into(self) -> Result<String, Self::Error> {
         match self {
             Value::Bytes(x) => String::from_utf8(x).map_err(Into::into),
+            Value::String(x) => Ok(x),
+            #[cfg(feature = "decimal")]
+            Value::Decimal(x) => Ok(x.to_string()),
             _ => Err(Self::Error::wrong_value(ValueType::Bytes, self)),
         }
     }
@@ -91,6 +97,7 @@ impl TryInto<Vec<u8>> for Value {
     fn try_into(self) -> Result<Vec<u8>, Self::Error> {
         match self {
             Value::Bytes(x) => Ok(x),
+            Value::String(x) => Ok(x.into_bytes()),
             _ => Err(Self::Error::wrong_value(ValueType::Bytes, self)),
         }
     }
@@ -164,6 +171,34 @@ impl TryInto<NaiveDateTime> for Value {
     }
 }
 
+/// Converted to UTC, as the protocol doesn't contain the time zone.
+/// The connection converts it to the session offset if [`ConnectionOptions::time_zone`](crate::ConnectionOptions::time_zone) is set.
+impl<Tz: TimeZone> TryFrom<DateTime<Tz>> for Value {
+    type Error = SerializeError;
+
+    fn try_from(value: DateTime<Tz>) -> Result<Self, Self::Error> {
+        value.naive_utc().try_into()
+    }
+}
+
+/// Interpreted as UTC. Rows are converted from the session offset if [`ConnectionOptions::time_zone`](crate::ConnectionOptions::time_zone) is set.
+impl TryInto<DateTime<Utc>> for Value {
+    type Error = ParseError;
+
+    fn try_into(self) -> Result<DateTime<Utc>, Self::Error> {
+        TryInto::<NaiveDateTime>::try_into(self).map(|x| x.and_utc())
+    }
+}
+
+/// Interpreted as UTC. Rows are converted from the session offset if [`ConnectionOptions::time_zone`](crate::ConnectionOptions::time_zone) is set.
+impl TryInto<DateTime<FixedOffset>> for Value {
+    type Error = ParseError;
+
+    fn try_into(self) -> Result<DateTime<FixedOffset>, Self::Error> {
+        TryInto::<NaiveDateTime>::try_into(self).map(|x| x.and_utc().fixed_offset())
+    }
+}
+
 impl From<Duration> for Value {
     fn from(value: Duration) -> Self {
         let seconds = value.num_seconds().abs();
@@ -222,6 +257,40 @@ impl TryInto<Duration> for Value {
     }
 }
 
+#[cfg(feature = "decimal")]
+#[cfg_attr(doc, doc(cfg(feature = "decimal")))]
+impl From<rust_decimal::Decimal> for Value {
+    fn from(value: rust_decimal::Decimal) -> Self {
+        Value::Decimal(value)
+    }
+}
+
+#[cfg(feature = "decimal")]
+#[cfg_attr(doc, doc(cfg(feature = "decimal")))]
+impl TryInto<rust_decimal::Decimal> for Value {
+    type Error = ParseError;
+
+    fn try_into(self) -> Result<rust_decimal::Decimal, Self::Error> {
+        match self {
+            Value::Decimal(x) => Ok(x),
+            Value::Tiny(x) => Ok(x.into()),
+            Value::Short(x) => Ok(x.into()),
+            Value::Int(x) => Ok(x.into()),
+            Value::Long(x) => Ok(x.into()),
+            Value::UTiny(x) => Ok(x.into()),
+            Value::UShort(x) => Ok(x.into()),
+            Value::UInt(x) => Ok(x.into()),
+            Value::ULong(x) => Ok(x.into()),
+            // `DECIMAL` values which don't fit are kept as bytes
+            Value::Bytes(x) => std::str::from_utf8(&x)
+                .ok()
+                .and_then(|s| rust_decimal::Decimal::from_str_exact(s).ok())
+                .ok_or(ParseError::InvalidValue(ValueType::Decimal, x)),
+            _ => Err(Self::Error::wrong_value(ValueType::Decimal, self)),
+        }
+    }
+}
+
 impl<T> TryFrom<Option<T>> for Value
 where
     T: TryInto<Value>,
@@ -256,3 +325,6 @@ pub(crate) use impl_try_into_option;
 
 impl_try_into_option!(i8, i16, i32, i64, u8, u16, u32, u64, f32, f64, bool);
 impl_try_into_option!(String, NaiveDate, NaiveDateTime, Duration, Vec<u8>);
+impl_try_into_option!(DateTime<Utc>, DateTime<FixedOffset>);
+#[cfg(feature = "decimal")]
+impl_try_into_option!(rust_decimal::Decimal);
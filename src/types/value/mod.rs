@@ -1,6 +1,9 @@
 mod bin;
 mod conversion;
 mod text;
+#[cfg(feature = "time-crate")]
+#[cfg_attr(doc, doc(cfg(feature = "time-crate")))]
+mod time_crate;
 
 pub(crate) use conversion::impl_try_into_option;
 pub use text::{IdentifierEscape, StringEscape, UnquotedIdentifierEscape};
@@ -11,7 +14,7 @@ use crate::connection::types::ColumnType;
 ///
 /// # Formatting
 /// Values of type `Date`, `Time` and `DateTime` will be put in quotation marks.
-/// Values of type `Bytes` will be converted to hex.
+/// Values of type `Bytes` will be converted to hex, values of type `String` will be escaped and quoted.
 /// If you only want to escape strings, use [`StringEscape`] instead.
 #[derive(Debug, Clone, PartialEq)]
 pub enum Value {
@@ -27,12 +30,19 @@ pub enum Value {
     Float(f32),
     Double(f64),
     Bytes(Vec<u8>),
+    /// UTF-8 string like a JSON document, which is formatted as quoted string literal.
+    /// Strings received from the server are [`Bytes`](Self::Bytes).
+    String(String),
     /// year, month, day
     Date(u16, u8, u8),
     /// is negative, days, hours, minutes, seconds, micro seconds
     Time(bool, u32, u8, u8, u8, u32),
     /// year, month, day, hour, minute, second, micro second
     Datetime(u16, u8, u8, u8, u8, u8, u32),
+    /// `DECIMAL` column, which is sent as string.
+    #[cfg(feature = "decimal")]
+    #[cfg_attr(doc, doc(cfg(feature = "decimal")))]
+    Decimal(rust_decimal::Decimal),
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -52,6 +62,20 @@ pub enum ValueType {
     Date,
     Time,
     Datetime,
+    Enum,
+    Set,
+    #[cfg(feature = "decimal")]
+    #[cfg_attr(doc, doc(cfg(feature = "decimal")))]
+    Decimal,
+    #[cfg(feature = "json")]
+    #[cfg_attr(doc, doc(cfg(feature = "json")))]
+    Json,
+    #[cfg(feature = "uuid")]
+    #[cfg_attr(doc, doc(cfg(feature = "uuid")))]
+    Uuid,
+    #[cfg(feature = "geo")]
+    #[cfg_attr(doc, doc(cfg(feature = "geo")))]
+    Geometry,
 }
 
 impl Value {
@@ -64,10 +88,12 @@ impl Value {
             Self::Long(_) | Value::ULong(_) => ColumnType::LongLong,
             Self::Float(_) => ColumnType::Float,
             Self::Double(_) => ColumnType::Double,
-            Self::Bytes(_) => ColumnType::VarString,
+            Self::Bytes(_) | Self::String(_) => ColumnType::VarString,
             Self::Date(..) => ColumnType::Date,
             Self::Time(..) => ColumnType::Time,
             Self::Datetime(..) => ColumnType::Datetime,
+            #[cfg(feature = "decimal")]
+            Self::Decimal(_) => ColumnType::NewDecimal,
         }
     }
 
@@ -165,10 +165,18 @@ impl fmt::Display for Value {
             Value::ULong(x) => x.fmt(f),
             Value::Float(x) => x.fmt(f),
             Value::Double(x) => x.fmt(f),
+            #[cfg(feature = "decimal")]
+            Value::Decimal(x) => x.fmt(f),
             Value::Bytes(x) => {
                 f.write_str("0x")?;
                 hex::encode(x).fmt(f)
             }
+            Value::String(x) => {
+                // single quotes also work with the `ANSI_QUOTES` sql mode
+                f.write_char('\'')?;
+                write_escaped_chars(f, x.chars())?;
+                f.write_char('\'')
+            }
             Value::Date(year, month, day) => {
                 write!(f, "\"")?;
                 format_date(f, year, month, day)?;
@@ -200,6 +208,18 @@ impl fmt::Display for Value {
 }
 
 impl Value {
+    /// Parses a decimal, which is sent as string by both protocols.
+    ///
+    /// Values which don't fit into a [`rust_decimal::Decimal`] (e.g. of `DECIMAL(65,30)` columns)
+    /// are kept as [`Value::Bytes`] instead of being rounded, so only converting them fails.
+    #[cfg(feature = "decimal")]
+    pub(super) fn deserialize_decimal(value: &[u8]) -> Self {
+        std::str::from_utf8(value)
+            .ok()
+            .and_then(|x| rust_decimal::Decimal::from_str_exact(x).ok())
+            .map_or_else(|| Self::Bytes(value.to_vec()), Self::Decimal)
+    }
+
     fn deserialize_time_text_inner(value: &[u8]) -> Result<(u32, u8, u8, u8, u32), ProtocolError> {
         let invalid_value = || ParseError::InvalidValue(ValueType::Time, value.to_vec());
         let first_colon = value
@@ -369,11 +389,15 @@ impl Value {
                     | ColumnType::MediumBlob
                     | ColumnType::Set
                     | ColumnType::Enum
-                    | ColumnType::Decimal
                     | ColumnType::Bit
-                    | ColumnType::NewDecimal
                     | ColumnType::Geometry
                     | ColumnType::Json => Ok(Self::Bytes(bytes.to_vec())),
+                    #[cfg(not(feature = "decimal"))]
+                    ColumnType::Decimal | ColumnType::NewDecimal => Ok(Self::Bytes(bytes.to_vec())),
+                    #[cfg(feature = "decimal")]
+                    ColumnType::Decimal | ColumnType::NewDecimal => {
+                        Ok(Self::deserialize_decimal(bytes))
+                    }
                     ColumnType::Timestamp | ColumnType::Datetime => {
                         Self::deserialize_datetime_text(bytes)
                     }
@@ -447,7 +471,7 @@ mod test {
             Value::Bytes(vec![32, 64, 123, 213]).to_string(),
             "0x20407bd5"
         );
-        assert_eq!(Value::Date(2038, 01, 19).to_string(), "\"2038-01-19\"");
+        assert_eq!(Value::Date(2038, 1, 19).to_string(), "\"2038-01-19\"");
         assert_eq!(
             Value::Time(false, 0, 4, 5, 8, 0).to_string(),
             "\"04:05:08\""
@@ -457,11 +481,11 @@ mod test {
             "\"148:15:18.000050\""
         );
         assert_eq!(
-            Value::Datetime(2038, 01, 19, 3, 14, 7, 0).to_string(),
+            Value::Datetime(2038, 1, 19, 3, 14, 7, 0).to_string(),
             "\"2038-01-19 03:14:07\""
         );
         assert_eq!(
-            Value::Datetime(2038, 01, 19, 3, 14, 7, 50).to_string(),
+            Value::Datetime(2038, 1, 19, 3, 14, 7, 50).to_string(),
             "\"2038-01-19 03:14:07.000050\""
         );
     }
@@ -482,11 +506,60 @@ mod test {
         );
         assert_eq!(
             Value::deserialize_datetime_text("2038-01-19 03:14:07".as_bytes()).unwrap(),
-            Value::Datetime(2038, 01, 19, 3, 14, 7, 0)
+            Value::Datetime(2038, 1, 19, 3, 14, 7, 0)
         );
         assert_eq!(
             Value::deserialize_datetime_text("2038-01-19 03:14:07.000050".as_bytes()).unwrap(),
-            Value::Datetime(2038, 01, 19, 3, 14, 7, 50)
+            Value::Datetime(2038, 1, 19, 3, 14, 7, 50)
+        );
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn decimal() {
+        use {
+            crate::{bitflags::ColumnFlags, connection::types::ColumnType, ParseBuf, Serialize},
+            rust_decimal::Decimal,
+            std::str::FromStr,
+        };
+
+        let decimal = Decimal::from_str("-12345678901234567890.123456").unwrap();
+        let value = Value::Decimal(decimal);
+        let mut buf = Vec::new();
+        value.serialize(&mut buf);
+        assert_eq!(buf.len() as u64, value.bin_len());
+        assert_eq!(&buf[1..], b"-12345678901234567890.123456");
+        assert_eq!(value.to_string(), "-12345678901234567890.123456");
+
+        for deserialize in [Value::deserialize_text, Value::deserialize_bin] {
+            let parsed = deserialize(
+                ColumnType::NewDecimal,
+                ColumnFlags::empty(),
+                &mut ParseBuf(&buf),
+            );
+            assert_eq!(parsed.unwrap(), value);
+        }
+        let mut buf = vec![40];
+        buf.extend_from_slice(b"1234567890123456789012345678901234567890");
+        for deserialize in [Value::deserialize_text, Value::deserialize_bin] {
+            let parsed = deserialize(
+                ColumnType::NewDecimal,
+                ColumnFlags::empty(),
+                &mut ParseBuf(&buf),
+            )
+            .unwrap();
+            assert_eq!(parsed, Value::Bytes(buf[1..].to_vec()));
+            assert!(TryInto::<Decimal>::try_into(parsed).is_err());
+        }
+        assert_eq!(
+            TryInto::<Decimal>::try_into(Value::Bytes(b"1.5".to_vec())).unwrap(),
+            Decimal::new(15, 1)
+        );
+
+        assert_eq!(TryInto::<Decimal>::try_into(value).unwrap(), decimal);
+        assert_eq!(
+            TryInto::<Decimal>::try_into(Value::UInt(5)).unwrap(),
+            Decimal::from(5)
         );
     }
 }
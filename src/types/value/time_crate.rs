@@ -0,0 +1,173 @@
+use {
+    super::{impl_try_into_option, Value, ValueType},
+    crate::{
+        error::{ParseError, SerializeError},
+        model::impl_from_query_result,
+    },
+    time::{Date, Duration, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset},
+};
+
+impl TryFrom<Date> for Value {
+    type Error = SerializeError;
+
+    fn try_from(value: Date) -> Result<Self, Self::Error> {
+        Ok(Value::Date(
+            value
+                .year()
+                .try_into()
+                .map_err(|_| SerializeError::InvalidValue(ValueType::Date, Box::new(value)))?,
+            value.month() as u8,
+            value.day(),
+        ))
+    }
+}
+
+impl TryFrom<PrimitiveDateTime> for Value {
+    type Error = SerializeError;
+
+    fn try_from(value: PrimitiveDateTime) -> Result<Self, Self::Error> {
+        Ok(Value::Datetime(
+            value
+                .year()
+                .try_into()
+                .map_err(|_| SerializeError::InvalidValue(ValueType::Datetime, Box::new(value)))?,
+            value.month() as u8,
+            value.day(),
+            value.hour(),
+            value.minute(),
+            value.second(),
+            value.microsecond(),
+        ))
+    }
+}
+
+/// Converted to UTC, as the protocol doesn't contain the time zone.
+/// The connection converts it to the session offset if [`ConnectionOptions::time_zone`](crate::ConnectionOptions::time_zone) is set.
+impl TryFrom<OffsetDateTime> for Value {
+    type Error = SerializeError;
+
+    fn try_from(value: OffsetDateTime) -> Result<Self, Self::Error> {
+        let value = value.to_offset(UtcOffset::UTC);
+        PrimitiveDateTime::new(value.date(), value.time()).try_into()
+    }
+}
+
+impl TryInto<Date> for Value {
+    type Error = ParseError;
+
+    fn try_into(self) -> Result<Date, Self::Error> {
+        match self {
+            Value::Date(year, month, day) | Value::Datetime(year, month, day, _, _, _, _) => {
+                Month::try_from(month)
+                    .and_then(|month| Date::from_calendar_date(year as i32, month, day))
+                    .map_err(|_| ParseError::ValueOutOfBounds(self))
+            }
+            _ => Err(Self::Error::wrong_value(ValueType::Date, self)),
+        }
+    }
+}
+
+impl TryInto<PrimitiveDateTime> for Value {
+    type Error = ParseError;
+
+    fn try_into(self) -> Result<PrimitiveDateTime, Self::Error> {
+        match self {
+            Value::Datetime(_, _, _, hour, minute, second, micro) => {
+                let time = Time::from_hms_micro(hour, minute, second, micro)
+                    .map_err(|_| ParseError::ValueOutOfBounds(self.clone()))?;
+                let date: Date = self.try_into()?;
+                Ok(PrimitiveDateTime::new(date, time))
+            }
+            _ => Err(Self::Error::wrong_value(ValueType::Datetime, self)),
+        }
+    }
+}
+
+/// Interpreted as UTC. Rows are converted from the session offset if [`ConnectionOptions::time_zone`](crate::ConnectionOptions::time_zone) is set.
+impl TryInto<OffsetDateTime> for Value {
+    type Error = ParseError;
+
+    fn try_into(self) -> Result<OffsetDateTime, Self::Error> {
+        TryInto::<PrimitiveDateTime>::try_into(self).map(PrimitiveDateTime::assume_utc)
+    }
+}
+
+impl From<Duration> for Value {
+    fn from(value: Duration) -> Self {
+        let seconds = value.whole_seconds().unsigned_abs();
+        let minutes = seconds / 60;
+        let hours = minutes / 60;
+        let days = hours / 24;
+
+        Value::Time(
+            value.is_negative(),
+            days as u32,
+            (hours % 24) as u8,
+            (minutes % 60) as u8,
+            (seconds % 60) as u8,
+            value.subsec_microseconds().unsigned_abs(),
+        )
+    }
+}
+
+impl TryInto<Duration> for Value {
+    type Error = ParseError;
+
+    fn try_into(self) -> Result<Duration, Self::Error> {
+        match self {
+            Value::Time(negative, days, hours, minutes, seconds, micros) => {
+                if micros >= 1_000_000 {
+                    return Err(ParseError::ValueOutOfBounds(self));
+                }
+                let duration = Duration::days(days as i64)
+                    + Duration::hours(hours as i64)
+                    + Duration::minutes(minutes as i64)
+                    + Duration::seconds(seconds as i64)
+                    + Duration::microseconds(micros as i64);
+                Ok(if negative { -duration } else { duration })
+            }
+            _ => Err(Self::Error::wrong_value(ValueType::Time, self)),
+        }
+    }
+}
+
+impl_try_into_option!(Date, PrimitiveDateTime, OffsetDateTime, Duration);
+impl_from_query_result!(Date, PrimitiveDateTime, OffsetDateTime, Duration);
+
+#[cfg(test)]
+mod test {
+    use {
+        crate::types::Value,
+        time::{Date, Duration, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset},
+    };
+
+    #[test]
+    fn conversion() {
+        let date = Date::from_calendar_date(2038, Month::January, 19).unwrap();
+        let datetime = PrimitiveDateTime::new(date, Time::from_hms_micro(3, 14, 7, 50).unwrap());
+        let value = Value::Datetime(2038, 1, 19, 3, 14, 7, 50);
+        assert_eq!(Value::try_from(date).unwrap(), Value::Date(2038, 1, 19));
+        assert_eq!(Value::try_from(datetime).unwrap(), value);
+        assert_eq!(
+            TryInto::<PrimitiveDateTime>::try_into(value.clone()).unwrap(),
+            datetime
+        );
+        assert_eq!(TryInto::<Date>::try_into(value.clone()).unwrap(), date);
+
+        let offset = datetime.assume_offset(UtcOffset::from_hms(2, 0, 0).unwrap());
+        assert_eq!(
+            Value::try_from(offset).unwrap(),
+            Value::Datetime(2038, 1, 19, 1, 14, 7, 50)
+        );
+        assert_eq!(
+            TryInto::<OffsetDateTime>::try_into(value).unwrap(),
+            datetime.assume_utc()
+        );
+
+        let duration = -(Duration::days(2) + Duration::minutes(3) + Duration::microseconds(4));
+        let value = Value::Time(true, 2, 0, 3, 0, 4);
+        assert_eq!(Value::from(duration), value);
+        assert_eq!(TryInto::<Duration>::try_into(value).unwrap(), duration);
+        assert!(TryInto::<Date>::try_into(Value::Date(2038, 13, 1)).is_err());
+    }
+}